@@ -15,5 +15,6 @@
 
 pub mod b64;
 pub mod env;
+pub mod position;
 pub mod time;
 pub mod token;
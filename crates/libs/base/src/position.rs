@@ -0,0 +1,372 @@
+//! Lexicographic position keys, for the manual ordering of items.
+//!
+//! Design:
+//!
+//! - A key is an integer part followed by a fractional part, both of base 62 digits
+//!   (`0-9A-Za-z`, in ASCII order), as in the "fractional indexing" scheme (e.g., `"a1V"`).
+//! - The integer part head (`a-z` for the positive, `A-Z` for the negative integers) gives
+//!   its length, so that the integers order lexicographically (e.g., `"a1"` < `"az"` < `"b10"`).
+//! - `key_between` returns a key strictly between two keys, so that an item can be moved
+//!   by updating its key only (no renumbering of the other items).
+//! - Appending (or prepending) increments (or decrements) the integer part, so that the keys
+//!   only grow logarithmically with the number of appends. Only the inserts between two
+//!   keys use the fractional part.
+//! - Fractional parts never end with `0`, otherwise no key could be inserted before `"a0V0"`
+//!   and after `"a0V"`.
+//! - Keys must be compared byte-wise (e.g., `COLLATE "C"` with Postgres).
+//!
+
+const DIGITS: &[u8] =
+	b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// The integer zero, i.e., the key of the first item.
+const INTEGER_ZERO: &str = "a0";
+
+/// The smallest integer, which cannot be decremented (and so is not a valid key alone).
+const INTEGER_SMALLEST: &str = "A00000000000000000000000000";
+
+/// Returns a key strictly between `before` and `after`,
+/// `None` being the start (for `before`) or end (for `after`) of the list.
+pub fn key_between(before: Option<&str>, after: Option<&str>) -> Result<String> {
+	for key in [before, after].into_iter().flatten() {
+		if !is_valid_key(key) {
+			return Err(Error::KeyInvalid(key.to_string()));
+		}
+	}
+
+	match (before, after) {
+		(None, None) => Ok(INTEGER_ZERO.to_string()),
+
+		// -- Prepend.
+		(None, Some(after)) => {
+			let (int_after, frac_after) = split_key(after);
+			if int_after == INTEGER_SMALLEST {
+				Ok(format!(
+					"{int_after}{}",
+					midpoint(b"", Some(frac_after.as_bytes()))
+				))
+			} else if !frac_after.is_empty() {
+				// e.g., before "a1V", "a1" is enough.
+				Ok(int_after.to_string())
+			} else {
+				match decrement_integer(int_after) {
+					// Note: The smallest integer needs a fractional part (see `INTEGER_SMALLEST`).
+					Some(int) if int == INTEGER_SMALLEST => {
+						Ok(format!("{int}{}", midpoint(b"", None)))
+					}
+					Some(int) => Ok(int),
+					None => Err(Error::KeyInvalid(after.to_string())),
+				}
+			}
+		}
+
+		// -- Append.
+		(Some(before), None) => {
+			let (int_before, frac_before) = split_key(before);
+			Ok(increment_integer(int_before).unwrap_or_else(|| {
+				format!("{int_before}{}", midpoint(frac_before.as_bytes(), None))
+			}))
+		}
+
+		// -- Insert.
+		(Some(before), Some(after)) => {
+			if before >= after {
+				return Err(Error::KeysNotOrdered {
+					before: before.to_string(),
+					after: after.to_string(),
+				});
+			}
+			let (int_before, frac_before) = split_key(before);
+			let (int_after, frac_after) = split_key(after);
+			if int_before == int_after {
+				let frac =
+					midpoint(frac_before.as_bytes(), Some(frac_after.as_bytes()));
+				return Ok(format!("{int_before}{frac}"));
+			}
+			match increment_integer(int_before) {
+				Some(int) if int.as_str() < after => Ok(int),
+				_ => Ok(format!(
+					"{int_before}{}",
+					midpoint(frac_before.as_bytes(), None)
+				)),
+			}
+		}
+	}
+}
+
+fn is_valid_key(key: &str) -> bool {
+	let Some(int_len) = key.bytes().next().and_then(integer_len) else {
+		return false;
+	};
+
+	key.len() >= int_len
+		&& key != INTEGER_SMALLEST
+		&& !key[int_len..].ends_with('0')
+		&& key.bytes().skip(1).all(|b| digit(b).is_some())
+}
+
+/// The integer part length (head included), given by its head.
+fn integer_len(head: u8) -> Option<usize> {
+	match head {
+		b'a'..=b'z' => Some((head - b'a') as usize + 2),
+		b'A'..=b'Z' => Some((b'Z' - head) as usize + 2),
+		_ => None,
+	}
+}
+
+/// Note: The key must be valid (see `is_valid_key`).
+fn split_key(key: &str) -> (&str, &str) {
+	let int_len = integer_len(key.as_bytes()[0]).unwrap_or(key.len());
+	key.split_at(int_len)
+}
+
+/// Returns `None` if the integer is the largest one.
+fn increment_integer(int: &str) -> Option<String> {
+	let (head, digits) = (int.as_bytes()[0], &int.as_bytes()[1..]);
+	let mut digits = digits.to_vec();
+
+	// -- Increment the digits (from the last one).
+	for d in digits.iter_mut().rev() {
+		let next = digit(*d).unwrap_or(0) + 1;
+		if next < DIGITS.len() {
+			*d = DIGITS[next];
+			return Some(to_key(head, &digits));
+		}
+		*d = DIGITS[0];
+	}
+
+	// -- Carry, to the next integer length.
+	match head {
+		b'z' => None,
+		b'Z' => Some(to_key(b'a', &DIGITS[..1])),
+		_ => {
+			let head = head + 1;
+			if head > b'a' {
+				digits.push(DIGITS[0]);
+			} else {
+				digits.pop();
+			}
+			Some(to_key(head, &digits))
+		}
+	}
+}
+
+/// Returns `None` if the integer is the smallest one.
+fn decrement_integer(int: &str) -> Option<String> {
+	let (head, digits) = (int.as_bytes()[0], &int.as_bytes()[1..]);
+	let mut digits = digits.to_vec();
+	let digit_last = DIGITS[DIGITS.len() - 1];
+
+	// -- Decrement the digits (from the last one).
+	for d in digits.iter_mut().rev() {
+		match digit(*d).unwrap_or(0) {
+			0 => *d = digit_last,
+			prev => {
+				*d = DIGITS[prev - 1];
+				return Some(to_key(head, &digits));
+			}
+		}
+	}
+
+	// -- Borrow, from the previous integer length.
+	match head {
+		b'A' => None,
+		b'a' => Some(to_key(b'Z', &[digit_last])),
+		_ => {
+			let head = head - 1;
+			if head < b'Z' {
+				digits.push(digit_last);
+			} else {
+				digits.pop();
+			}
+			Some(to_key(head, &digits))
+		}
+	}
+}
+
+fn to_key(head: u8, digits: &[u8]) -> String {
+	let mut key = String::with_capacity(digits.len() + 1);
+	key.push(head as char);
+	key.extend(digits.iter().map(|d| *d as char));
+	key
+}
+
+/// The fractional part strictly between `before` and `after`.
+///
+/// Note: `before` might be empty (i.e., 0), `after` is `None` for 1.
+fn midpoint(before: &[u8], after: Option<&[u8]>) -> String {
+	// -- Common prefix (`before` padded with `0`s).
+	if let Some(after) = after {
+		let n = after
+			.iter()
+			.enumerate()
+			.take_while(|(i, b)| before.get(*i).copied().unwrap_or(b'0') == **b)
+			.count();
+		if n > 0 {
+			let prefix = String::from_utf8_lossy(&after[..n]).to_string();
+			let before = before.get(n..).unwrap_or_default();
+			return prefix + &midpoint(before, Some(&after[n..]));
+		}
+	}
+
+	// -- First digits differ.
+	let digit_before = before.first().and_then(|b| digit(*b)).unwrap_or(0);
+	let digit_after = after
+		.and_then(|after| after.first())
+		.and_then(|b| digit(*b))
+		.unwrap_or(DIGITS.len());
+
+	if digit_after - digit_before > 1 {
+		let mid = (digit_before + digit_after).div_ceil(2);
+		(DIGITS[mid] as char).to_string()
+	} else if let Some(after) = after.filter(|after| after.len() > 1) {
+		// e.g., between "1" and "2V", "2" is enough.
+		(after[0] as char).to_string()
+	} else {
+		let rest = before.get(1..).unwrap_or_default();
+		(DIGITS[digit_before] as char).to_string() + &midpoint(rest, None)
+	}
+}
+
+fn digit(b: u8) -> Option<usize> {
+	DIGITS.iter().position(|d| *d == b)
+}
+
+// region:    --- Error
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+	KeyInvalid(String),
+	KeysNotOrdered { before: String, after: String },
+}
+
+// region:    --- Error Boilerplate
+impl core::fmt::Display for Error {
+	fn fmt(
+		&self,
+		fmt: &mut core::fmt::Formatter,
+	) -> core::result::Result<(), core::fmt::Error> {
+		write!(fmt, "{self:?}")
+	}
+}
+
+impl std::error::Error for Error {}
+// endregion: --- Error Boilerplate
+
+// endregion: --- Error
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+
+	#[test]
+	fn test_key_between_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_cases: &[(Option<&str>, Option<&str>, &str)] = &[
+			(None, None, "a0"),
+			(Some("a0"), None, "a1"),
+			(None, Some("a0"), "Zz"),
+			(Some("a0"), Some("a1"), "a0V"),
+			(Some("a1"), Some("a2V"), "a2"),
+			(Some("a0V"), Some("a0V1"), "a0V0V"),
+			(Some("az"), None, "b00"),
+			(Some("Zz"), None, "a0"),
+			(None, Some("b00"), "az"),
+			(None, Some("a1V"), "a1"),
+			(
+				Some("zzzzzzzzzzzzzzzzzzzzzzzzzzz"),
+				None,
+				"zzzzzzzzzzzzzzzzzzzzzzzzzzzV",
+			),
+			(
+				None,
+				Some("A00000000000000000000000001"),
+				"A00000000000000000000000000V",
+			),
+		];
+
+		for (before, after, expected) in fx_cases {
+			// -- Exec
+			let key = key_between(*before, *after)?;
+
+			// -- Check
+			assert_eq!(&key, expected, "before: {before:?}, after: {after:?}");
+			assert!(before.map(|b| b < key.as_str()).unwrap_or(true));
+			assert!(after.map(|a| key.as_str() < a).unwrap_or(true));
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_key_between_repeated_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mut before = key_between(None, None)?;
+		let after = key_between(Some(&before), None)?;
+
+		// -- Exec & Check
+		// Always inserting right after `before`, i.e., the worst case for the key length.
+		for _ in 0..100 {
+			let key = key_between(Some(&before), Some(&after))?;
+			assert!(before < key && key < after, "{before} < {key} < {after}");
+			before = key;
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_key_between_append_prepend_len_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_count = 100_000;
+		let fx_len_max = 4;
+		let mut last = key_between(None, None)?;
+		let mut first = last.clone();
+
+		// -- Exec & Check
+		for _ in 0..fx_count {
+			let key = key_between(Some(&last), None)?;
+			assert!(last < key, "{last} < {key}");
+			last = key;
+
+			let key = key_between(None, Some(&first))?;
+			assert!(key < first, "{key} < {first}");
+			first = key;
+		}
+		assert!(last.len() <= fx_len_max, "Key too long: {last}");
+		assert!(first.len() <= fx_len_max, "Key too long: {first}");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_key_between_err() -> Result<()> {
+		// -- Exec & Check
+		assert!(matches!(
+			key_between(Some("a2"), Some("a1")),
+			Err(Error::KeysNotOrdered { .. })
+		));
+		assert!(matches!(
+			key_between(Some("a0V0"), None),
+			Err(Error::KeyInvalid(_))
+		));
+		assert!(matches!(
+			key_between(None, Some("a-")),
+			Err(Error::KeyInvalid(_))
+		));
+		assert!(matches!(
+			key_between(None, Some("b1")),
+			Err(Error::KeyInvalid(_))
+		));
+		assert!(matches!(
+			key_between(None, Some("A00000000000000000000000000")),
+			Err(Error::KeyInvalid(_))
+		));
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
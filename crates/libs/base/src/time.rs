@@ -1,5 +1,5 @@
 use time::format_description::well_known::Rfc3339;
-use time::{Duration, OffsetDateTime};
+use time::{Duration, Month, OffsetDateTime};
 
 pub fn now_utc() -> OffsetDateTime {
 	OffsetDateTime::now_utc()
@@ -19,6 +19,46 @@ pub fn parse_utc(moment: &str) -> Result<OffsetDateTime> {
 		.map_err(|_| Error::DateFailParse(moment.to_string()))
 }
 
+// region:    --- Date Arithmetic
+
+/// Add (or subtract) days, failing with `Error::DateOutOfRange` on overflow.
+pub fn add_days(time: OffsetDateTime, days: i64) -> Result<OffsetDateTime> {
+	days.checked_mul(86_400)
+		.map(Duration::seconds)
+		.and_then(|duration| time.checked_add(duration))
+		.ok_or(Error::DateOutOfRange)
+}
+
+/// Add (or subtract) calendar months, keeping the time of day.
+///
+/// Note: The day is clamped to the last day of the target month
+///       (e.g., Jan 31 + 1 month is Feb 28 or 29).
+pub fn add_months(time: OffsetDateTime, months: i32) -> Result<OffsetDateTime> {
+	let month_index = time
+		.year()
+		.checked_mul(12)
+		.and_then(|m| m.checked_add(time.month() as i32 - 1))
+		.and_then(|m| m.checked_add(months))
+		.ok_or(Error::DateOutOfRange)?;
+	let year = month_index.div_euclid(12);
+	let month = Month::try_from((month_index.rem_euclid(12) + 1) as u8)
+		.map_err(|_| Error::DateOutOfRange)?;
+	let day = time.day().min(month.length(year));
+
+	time.replace_day(1)
+		.and_then(|t| t.replace_year(year))
+		.and_then(|t| t.replace_month(month))
+		.and_then(|t| t.replace_day(day))
+		.map_err(|_| Error::DateOutOfRange)
+}
+
+/// The number of days from Monday (0) to Sunday (6).
+pub fn weekday_index(time: OffsetDateTime) -> u8 {
+	time.weekday().number_days_from_monday()
+}
+
+// endregion: --- Date Arithmetic
+
 // region:    --- Error
 
 pub type Result<T> = core::result::Result<T, Error>;
@@ -26,6 +66,7 @@ pub type Result<T> = core::result::Result<T, Error>;
 #[derive(Debug)]
 pub enum Error {
 	DateFailParse(String),
+	DateOutOfRange,
 }
 
 // region:    --- Error Boilerplate
@@ -42,3 +83,55 @@ impl std::error::Error for Error {}
 // endregion: --- Error Boilerplate
 
 // endregion: --- Error
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+
+	#[test]
+	fn test_add_months_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_cases = &[
+			("2024-01-31T10:00:00Z", 1, "2024-02-29T10:00:00Z"),
+			("2023-01-31T10:00:00Z", 1, "2023-02-28T10:00:00Z"),
+			("2024-11-15T10:00:00Z", 3, "2025-02-15T10:00:00Z"),
+			("2024-03-31T10:00:00Z", -1, "2024-02-29T10:00:00Z"),
+			("2024-02-29T10:00:00Z", 12, "2025-02-28T10:00:00Z"),
+		];
+
+		for (time, months, expected) in fx_cases {
+			// -- Exec
+			let res = add_months(parse_utc(time)?, *months)?;
+
+			// -- Check
+			assert_eq!(format_time(res), *expected, "{time} + {months} months");
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_add_err_out_of_range() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_time = parse_utc("2024-01-31T10:00:00Z")?;
+
+		// -- Exec & Check
+		assert!(matches!(
+			add_days(fx_time, 10_000_000),
+			Err(Error::DateOutOfRange)
+		));
+		assert!(matches!(
+			add_days(fx_time, i64::MAX),
+			Err(Error::DateOutOfRange)
+		));
+		assert!(matches!(
+			add_months(fx_time, i32::MAX),
+			Err(Error::DateOutOfRange)
+		));
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
@@ -7,7 +7,8 @@
 //! - Each segment is encoded using base64 URL encoding to ensure maximum portability.
 //! - Tokens can be used for various purposes such as Web tokens, password reset tokens,
 //!   signed URLs, or any other application that requires a string identifier with an expiration.
-//! - This Token implementation is utilized in `services/web-server/src/web/web_token.rs`.
+//! - This Token implementation is utilized in `services/web-server/src/web/web_token.rs`,
+//!   `services/web-server/src/web/pwd_reset_token.rs`, and `.../web/email_verify_token.rs`.
 //! - Currently, the token employs a singular encryption scheme, which is typically sufficient.
 //!   However, if necessary, it can be expanded to support multiple schemes (refer to `core/pwd/` for an example of a multi-scheme pattern).
 //!
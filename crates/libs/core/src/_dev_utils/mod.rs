@@ -10,6 +10,9 @@ use tracing::info;
 
 // endregion: --- Modules
 
+/// The dev seed "demo" workspace (see `sql/dev_initial/02-dev-seed.sql`).
+pub const DEMO_WORKSPACE_ID: i64 = 1000;
+
 // Initialize environment for local development.
 // (for early development, called from `main()`)
 pub async fn init_dev() {
@@ -45,9 +48,15 @@ pub async fn seed_tasks(
 	let mut tasks = Vec::new();
 
 	for title in titles {
-		let id =
-			TaskBmc::create(ctx, mm, TaskForCreate { title: title.to_string() })
-				.await?;
+		let id = TaskBmc::create(
+			ctx,
+			mm,
+			TaskForCreate {
+				title: title.to_string(),
+				..Default::default()
+			},
+		)
+		.await?;
 		let task = TaskBmc::get(ctx, mm, id).await?;
 
 		tasks.push(task);
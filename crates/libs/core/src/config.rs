@@ -19,10 +19,20 @@ pub struct Config {
 	// -- Token
 	pub TOKEN_KEY: Vec<u8>,
 	pub TOKEN_DURATION_SEC: f64,
+	pub PWD_RESET_DURATION_SEC: f64,
+	pub EMAIL_VERIFY_DURATION_SEC: f64,
 
 	// -- Db
 	pub DB_URL: String,
 
+	// -- Register
+	pub REGISTER_ENABLED: bool,
+	pub EMAIL_VERIFY_REQUIRED: bool,
+
+	// -- Blob
+	pub BLOB_FOLDER: String,
+	pub ATTACHMENT_MAX_SIZE: usize,
+
 	// -- Web
 	pub WEB_FOLDER: String,
 }
@@ -36,10 +46,22 @@ impl Config {
 			// -- Token
 			TOKEN_KEY: get_env_b64u_as_u8s("SERVICE_TOKEN_KEY")?,
 			TOKEN_DURATION_SEC: get_env_parse("SERVICE_TOKEN_DURATION_SEC")?,
+			PWD_RESET_DURATION_SEC: get_env_parse("SERVICE_PWD_RESET_DURATION_SEC")?,
+			EMAIL_VERIFY_DURATION_SEC: get_env_parse(
+				"SERVICE_EMAIL_VERIFY_DURATION_SEC",
+			)?,
 
 			// -- Db
 			DB_URL: get_env("SERVICE_DB_URL")?,
 
+			// -- Register
+			REGISTER_ENABLED: get_env_parse("SERVICE_REGISTER_ENABLED")?,
+			EMAIL_VERIFY_REQUIRED: get_env_parse("SERVICE_EMAIL_VERIFY_REQUIRED")?,
+
+			// -- Blob
+			BLOB_FOLDER: get_env("SERVICE_BLOB_FOLDER")?,
+			ATTACHMENT_MAX_SIZE: get_env_parse("SERVICE_ATTACHMENT_MAX_SIZE")?,
+
 			// -- Web
 			WEB_FOLDER: get_env("SERVICE_WEB_FOLDER")?,
 		})
@@ -51,6 +73,7 @@ impl Config {
 pub type Result<T> = core::result::Result<T, Error>;
 
 #[derive(Debug)]
+#[allow(dead_code)] // Fields only read through Debug (panic message).
 pub enum Error {
 	ConfigEnvFail(env::Error),
 }
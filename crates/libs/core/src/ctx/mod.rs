@@ -3,26 +3,80 @@ mod error;
 
 pub use self::error::{Error, Result};
 
+use uuid::Uuid;
+
 // endregion: --- Modules
 
 #[derive(Clone, Debug)]
 pub struct Ctx {
 	user_id: i64,
+
+	/// The active workspace (i.e., tenant) of the request.
+	workspace_id: Option<i64>,
+
+	/// The session of the request (i.e., of its web token).
+	session_id: Option<i64>,
+
+	/// The request uuid (for the audit log).
+	req_uuid: Option<Uuid>,
+
+	// -- RBAC (resolved from the user roles)
+	roles: Vec<String>,
+	permissions: Vec<String>,
 }
 
 // Constructors.
 impl Ctx {
 	pub fn root_ctx() -> Self {
-		Ctx { user_id: 0 }
+		Ctx {
+			user_id: 0,
+			workspace_id: None,
+			session_id: None,
+			req_uuid: None,
+			roles: Vec::new(),
+			permissions: Vec::new(),
+		}
 	}
 
 	pub fn new(user_id: i64) -> Result<Self> {
 		if user_id == 0 {
 			Err(Error::CtxCannotNewRootCtx)
 		} else {
-			Ok(Self { user_id })
+			Ok(Self {
+				user_id,
+				workspace_id: None,
+				session_id: None,
+				req_uuid: None,
+				roles: Vec::new(),
+				permissions: Vec::new(),
+			})
 		}
 	}
+
+	pub fn with_workspace_id(mut self, workspace_id: i64) -> Self {
+		self.workspace_id = Some(workspace_id);
+		self
+	}
+
+	pub fn with_session_id(mut self, session_id: i64) -> Self {
+		self.session_id = Some(session_id);
+		self
+	}
+
+	pub fn with_req_uuid(mut self, req_uuid: Uuid) -> Self {
+		self.req_uuid = Some(req_uuid);
+		self
+	}
+
+	pub fn with_rbac(
+		mut self,
+		roles: Vec<String>,
+		permissions: Vec<String>,
+	) -> Self {
+		self.roles = roles;
+		self.permissions = permissions;
+		self
+	}
 }
 
 // Property Accessors.
@@ -30,4 +84,40 @@ impl Ctx {
 	pub fn user_id(&self) -> i64 {
 		self.user_id
 	}
+
+	pub fn is_root(&self) -> bool {
+		self.user_id == 0
+	}
+
+	pub fn workspace_id(&self) -> Option<i64> {
+		self.workspace_id
+	}
+
+	pub fn session_id(&self) -> Option<i64> {
+		self.session_id
+	}
+
+	pub fn req_uuid(&self) -> Option<Uuid> {
+		self.req_uuid
+	}
+
+	pub fn roles(&self) -> &[String] {
+		&self.roles
+	}
+
+	pub fn permissions(&self) -> &[String] {
+		&self.permissions
+	}
+}
+
+// RBAC.
+impl Ctx {
+	pub fn has_role(&self, role: &str) -> bool {
+		self.roles.iter().any(|r| r == role)
+	}
+
+	/// Note: The root ctx has all permissions.
+	pub fn has_permission(&self, permission: &str) -> bool {
+		self.is_root() || self.permissions.iter().any(|p| p == permission)
+	}
 }
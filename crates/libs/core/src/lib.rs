@@ -5,7 +5,7 @@
 //! - The `core` library provides core functionalities for application services
 //!   (e.g., Web Service and Job Service).
 //! - Key sub-modules within this library include the `Ctx`, `Model`, and eventually the `Event` layer,
-//!    which offer essential implementations for accessing application data and services.
+//!   which offer essential implementations for accessing application data and services.
 //!
 //!
 //! Notes:
@@ -0,0 +1,451 @@
+use crate::config;
+use crate::ctx::Ctx;
+use crate::model::base::{self, DbBmc, HasId};
+use crate::model::blob::BlobReader;
+use crate::model::filter::{
+	FilterNode, FilterNodes, IntoFilterNodes, OpValsInt64, OpValsString, OpValsTime,
+};
+use crate::model::task::TaskBmc;
+use crate::model::{Error, ModelManager, Result};
+use serde::Serialize;
+use serde_with::serde_as;
+use sha2::{Digest, Sha256};
+use sqlb::Fields;
+use sqlx::FromRow;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+// region:    --- Attachment Types
+#[serde_as]
+#[derive(Clone, Fields, FromRow, Debug, Serialize)]
+pub struct Attachment {
+	pub id: i64,
+	pub workspace_id: i64,
+	pub task_id: i64,
+
+	pub file_name: String,
+	/// Sniffed from the content (the client one is not trusted).
+	pub content_type: String,
+	pub size: i64,
+	/// SHA-256 (hex) of the content.
+	pub checksum: String,
+	#[serde(skip)]
+	pub blob_key: String,
+
+	// -- Timestamps
+	pub cid: i64,
+	#[serde_as(as = "Rfc3339")]
+	pub ctime: OffsetDateTime,
+	pub mid: i64,
+	#[serde_as(as = "Rfc3339")]
+	pub mtime: OffsetDateTime,
+}
+
+impl HasId for Attachment {
+	fn id(&self) -> i64 {
+		self.id
+	}
+}
+
+pub struct AttachmentForCreate {
+	pub task_id: i64,
+	pub file_name: String,
+}
+
+#[derive(Fields)]
+struct AttachmentForInsert {
+	task_id: i64,
+	file_name: String,
+	content_type: String,
+	size: i64,
+	checksum: String,
+	blob_key: String,
+}
+
+#[derive(Debug, Default)]
+pub struct AttachmentFilter {
+	pub id: Option<OpValsInt64>,
+	pub task_id: Option<OpValsInt64>,
+	pub file_name: Option<OpValsString>,
+
+	pub cid: Option<OpValsInt64>,
+	pub ctime: Option<OpValsTime>,
+	pub mid: Option<OpValsInt64>,
+	pub mtime: Option<OpValsTime>,
+}
+
+impl FilterNodes for AttachmentFilter {
+	fn filter_nodes(self) -> Vec<FilterNode> {
+		[
+			self.id.into_filter_nodes("id"),
+			self.task_id.into_filter_nodes("task_id"),
+			self.file_name.into_filter_nodes("file_name"),
+			self.cid.into_filter_nodes("cid"),
+			self.ctime.into_filter_nodes("ctime"),
+			self.mid.into_filter_nodes("mid"),
+			self.mtime.into_filter_nodes("mtime"),
+		]
+		.into_iter()
+		.flatten()
+		.collect()
+	}
+}
+// endregion: --- Attachment Types
+
+/// The max length of the attachment file name (see `attachment.file_name`).
+const FILE_NAME_MAX_LEN: usize = 256;
+
+pub struct AttachmentBmc;
+
+/// Note: No access policy of its own, the attachments of a task are accessible
+///       to the ones who can access the task (as for the comments).
+impl DbBmc for AttachmentBmc {
+	const TABLE: &'static str = "attachment";
+	const HAS_TIMESTAMPS: bool = true;
+	const WORKSPACE_SCOPED: bool = true;
+}
+
+impl AttachmentBmc {
+	/// Store the content in the blob store, and create the attachment
+	/// with its sniffed content type and checksum.
+	///
+	/// Note: The content must not be over `config().ATTACHMENT_MAX_SIZE`.
+	pub async fn create(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		attachment_c: AttachmentForCreate,
+		content: &[u8],
+	) -> Result<i64> {
+		let AttachmentForCreate { task_id, file_name } = attachment_c;
+
+		TaskBmc::get(ctx, mm, task_id).await?;
+
+		// -- Validate the content size and file name.
+		let max_size = config().ATTACHMENT_MAX_SIZE;
+		if content.len() > max_size {
+			return Err(Error::AttachmentTooLarge { size: content.len(), max_size });
+		}
+		// Note: Only the last path component (some clients send the full path),
+		//       without the control chars and quotes (not valid in the download headers).
+		let file_name: String = file_name
+			.rsplit(['/', '\\'])
+			.next()
+			.unwrap_or_default()
+			.chars()
+			.filter(|c| !c.is_control() && *c != '"')
+			.collect();
+		let file_name = file_name.trim();
+		if file_name.is_empty() || file_name.chars().count() > FILE_NAME_MAX_LEN {
+			return Err(Error::AttachmentFileNameInvalid(file_name.to_string()));
+		}
+
+		// -- Store the blob (before the row, see `blob`).
+		let blob_key = Uuid::new_v4().to_string();
+		mm.blob_store().put(&blob_key, content).await?;
+
+		let attachment_i = AttachmentForInsert {
+			task_id,
+			file_name: file_name.to_string(),
+			content_type: sniff_content_type(content).to_string(),
+			size: content.len() as i64,
+			checksum: sha256_hex(content),
+			blob_key: blob_key.clone(),
+		};
+
+		match base::create::<Self, _>(ctx, mm, attachment_i).await {
+			Ok(id) => Ok(id),
+			Err(ex) => {
+				mm.blob_store().delete(&blob_key).await?;
+				Err(ex)
+			}
+		}
+	}
+
+	pub async fn get(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Attachment> {
+		let attachment: Attachment = base::get::<Self, _>(ctx, mm, id).await?;
+		TaskBmc::get(ctx, mm, attachment.task_id).await?;
+
+		Ok(attachment)
+	}
+
+	/// Get the attachment with its content reader.
+	pub async fn open(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		id: i64,
+	) -> Result<(Attachment, BlobReader)> {
+		let attachment = Self::get(ctx, mm, id).await?;
+		let reader = mm.blob_store().open(&attachment.blob_key).await?;
+
+		Ok((attachment, reader))
+	}
+
+	/// List the attachments of a task (oldest first).
+	pub async fn list_by_task(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		task_id: i64,
+	) -> Result<Vec<Attachment>> {
+		TaskBmc::get(ctx, mm, task_id).await?;
+
+		let filter = AttachmentFilter {
+			task_id: Some(OpValsInt64 { eq: Some(task_id), ..Default::default() }),
+			..Default::default()
+		};
+
+		base::list::<Self, _, _>(ctx, mm, Some(filter), None).await
+	}
+
+	/// Delete the attachment and its blob.
+	pub async fn delete(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+		let mm = mm.begin().await?;
+
+		let attachment = Self::get(ctx, &mm, id).await?;
+		base::delete::<Self>(ctx, &mm, id).await?;
+		mm.delete_blobs(vec![attachment.blob_key]).await?;
+
+		mm.commit().await?;
+
+		Ok(())
+	}
+
+	/// The blob keys of the attachments of the task and its subtasks
+	/// (for `TaskBmc::purge`, since their rows are deleted by cascade).
+	pub(crate) async fn list_blob_keys_by_task_tree(
+		mm: &ModelManager,
+		task_id: i64,
+	) -> Result<Vec<String>> {
+		let query = sqlx::query_as(
+			r#"WITH RECURSIVE tree AS (
+			     SELECT $1::bigint AS id
+			     UNION
+			     SELECT task.id FROM task
+			     JOIN tree ON task.parent_id = tree.id
+			   )
+			   SELECT blob_key FROM attachment WHERE task_id IN (SELECT id FROM tree)"#,
+		)
+		.bind(task_id);
+		let blob_keys: Vec<(String,)> = mm.dbx().fetch_all_as(query).await?;
+
+		Ok(blob_keys.into_iter().map(|(blob_key,)| blob_key).collect())
+	}
+
+	/// The blob keys of the attachments of the project tasks and their subtasks
+	/// (for `ProjectBmc::delete`, since their rows are deleted by cascade).
+	pub(crate) async fn list_blob_keys_by_project(
+		mm: &ModelManager,
+		project_id: i64,
+	) -> Result<Vec<String>> {
+		let query = sqlx::query_as(
+			r#"WITH RECURSIVE tree AS (
+			     SELECT id FROM task WHERE project_id = $1
+			     UNION
+			     SELECT task.id FROM task
+			     JOIN tree ON task.parent_id = tree.id
+			   )
+			   SELECT blob_key FROM attachment WHERE task_id IN (SELECT id FROM tree)"#,
+		)
+		.bind(project_id);
+		let blob_keys: Vec<(String,)> = mm.dbx().fetch_all_as(query).await?;
+
+		Ok(blob_keys.into_iter().map(|(blob_key,)| blob_key).collect())
+	}
+}
+
+// region:    --- Content Utils
+
+/// The content type magic numbers (file signature prefixes).
+const MAGIC_NUMBERS: &[(&[u8], &str)] = &[
+	(b"\x89PNG\r\n\x1a\n", "image/png"),
+	(b"\xff\xd8\xff", "image/jpeg"),
+	(b"GIF87a", "image/gif"),
+	(b"GIF89a", "image/gif"),
+	(b"%PDF-", "application/pdf"),
+	(b"PK\x03\x04", "application/zip"),
+	(b"\x1f\x8b", "application/gzip"),
+];
+
+fn sniff_content_type(content: &[u8]) -> &'static str {
+	if let Some((_, content_type)) = MAGIC_NUMBERS
+		.iter()
+		.find(|(magic, _)| content.starts_with(magic))
+	{
+		return content_type;
+	}
+
+	// e.g., "RIFF....WEBP"
+	if content.len() >= 12 && &content[..4] == b"RIFF" && &content[8..12] == b"WEBP"
+	{
+		"image/webp"
+	} else if std::str::from_utf8(content).is_ok() {
+		"text/plain; charset=utf-8"
+	} else {
+		"application/octet-stream"
+	}
+}
+
+fn sha256_hex(content: &[u8]) -> String {
+	Sha256::digest(content)
+		.iter()
+		.map(|b| format!("{b:02x}"))
+		.collect()
+}
+
+// endregion: --- Content Utils
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::_dev_utils::{self, DEMO_WORKSPACE_ID};
+	use crate::model::task::TaskForCreate;
+	use anyhow::Result;
+	use serial_test::serial;
+	use tokio::io::AsyncReadExt;
+
+	#[serial]
+	#[tokio::test]
+	async fn test_create_open_delete_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_content = b"\x89PNG\r\n\x1a\n - test_create_open_delete_ok";
+		let task_id = TaskBmc::create(
+			&ctx,
+			&mm,
+			TaskForCreate {
+				title: "test_create_open_delete_ok - task 01".to_string(),
+				..Default::default()
+			},
+		)
+		.await?;
+
+		// -- Exec
+		let attachment_c = AttachmentForCreate {
+			task_id,
+			file_name: "some/path/\"image\"\t01.png".to_string(),
+		};
+		let id = AttachmentBmc::create(&ctx, &mm, attachment_c, fx_content).await?;
+		let (attachment, mut reader) = AttachmentBmc::open(&ctx, &mm, id).await?;
+		let mut content = Vec::new();
+		reader.read_to_end(&mut content).await?;
+
+		// -- Check
+		assert_eq!(attachment.file_name, "image01.png");
+		assert_eq!(attachment.content_type, "image/png");
+		assert_eq!(attachment.size, fx_content.len() as i64);
+		assert_eq!(attachment.checksum, sha256_hex(fx_content));
+		assert_eq!(content, fx_content);
+
+		// -- Exec & Check - delete (with the blob)
+		AttachmentBmc::delete(&ctx, &mm, id).await?;
+		let res = mm.blob_store().open(&attachment.blob_key).await;
+		assert!(res.is_err(), "Blob should be deleted");
+
+		// -- Clean
+		TaskBmc::purge(&ctx, &mm, task_id).await?;
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_delete_rollback_keep_blob_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_content = b"test_delete_rollback_keep_blob_ok";
+		let task_id = TaskBmc::create(
+			&ctx,
+			&mm,
+			TaskForCreate {
+				title: "test_delete_rollback_keep_blob_ok - task 01".to_string(),
+				..Default::default()
+			},
+		)
+		.await?;
+		let attachment_c = AttachmentForCreate {
+			task_id,
+			file_name: "file 01.txt".to_string(),
+		};
+		let id = AttachmentBmc::create(&ctx, &mm, attachment_c, fx_content).await?;
+		let attachment = AttachmentBmc::get(&ctx, &mm, id).await?;
+
+		// -- Exec
+		{
+			let mm = mm.begin().await?;
+			AttachmentBmc::delete(&ctx, &mm, id).await?;
+			TaskBmc::purge(&ctx, &mm, task_id).await?;
+			mm.rollback().await?;
+		}
+
+		// -- Check
+		let (_, mut reader) = AttachmentBmc::open(&ctx, &mm, id).await?;
+		let mut content = Vec::new();
+		reader.read_to_end(&mut content).await?;
+		assert_eq!(content, fx_content);
+
+		// -- Exec & Check - purge (with the blob, after the commit)
+		TaskBmc::purge(&ctx, &mm, task_id).await?;
+		let res = mm.blob_store().open(&attachment.blob_key).await;
+		assert!(res.is_err(), "Blob should be deleted");
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_create_err_too_large() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_content = vec![0u8; config().ATTACHMENT_MAX_SIZE + 1];
+		let task_id = TaskBmc::create(
+			&ctx,
+			&mm,
+			TaskForCreate {
+				title: "test_create_err_too_large - task 01".to_string(),
+				..Default::default()
+			},
+		)
+		.await?;
+
+		// -- Exec
+		let attachment_c = AttachmentForCreate {
+			task_id,
+			file_name: "file 01.bin".to_string(),
+		};
+		let res = AttachmentBmc::create(&ctx, &mm, attachment_c, &fx_content).await;
+
+		// -- Check
+		assert!(
+			matches!(res, Err(Error::AttachmentTooLarge { .. })),
+			"Should be too large. Actual: {res:?}"
+		);
+
+		// -- Clean
+		TaskBmc::purge(&ctx, &mm, task_id).await?;
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_sniff_content_type_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_cases: &[(&[u8], &str)] = &[
+			(b"%PDF-1.7 ...", "application/pdf"),
+			(b"RIFF\x00\x00\x00\x00WEBPVP8 ", "image/webp"),
+			(b"Hello, world!", "text/plain; charset=utf-8"),
+			(b"\x00\xff\xfe", "application/octet-stream"),
+		];
+
+		for (content, expected) in fx_cases {
+			// -- Exec & Check
+			assert_eq!(sniff_content_type(content), *expected);
+		}
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
@@ -0,0 +1,362 @@
+//! Audit log model.
+//!
+//! - `model::base` records an `audit_log` entry for each create/update/delete
+//!   (and restore/purge), in the same transaction as the change.
+//! - An entry has the `before` and `after` row json (only the changed properties
+//!   for an update), without the `DbBmc::AUDIT_EXCLUDED_COLUMNS`.
+//! - Writes made outside of `model::base` (e.g., `UserBmc::update_pwd`) are not audited.
+//! - An entry has the ctx workspace of the write, and is listed only in that workspace
+//!   (like the `DbBmc::WORKSPACE_SCOPED` entities).
+//!
+
+use crate::ctx::Ctx;
+use crate::model::base::{self, DbBmc, HasId};
+use crate::model::filter::{
+	FilterNode, FilterNodes, IntoFilterNodes, ListOptions, OpValsInt64,
+	OpValsString, OpValsTime, Page,
+};
+use crate::model::{ModelManager, Result};
+use lib_base::time::now_utc;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_with::{serde_as, DisplayFromStr};
+use sqlb::{Fields, SqlxBindable};
+use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueRef};
+use sqlx::{Decode, Encode, FromRow, Postgres, Type};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+// region:    --- AuditEntry Types
+#[serde_as]
+#[derive(Clone, Fields, FromRow, Debug, Serialize)]
+pub struct AuditEntry {
+	pub id: i64,
+	pub workspace_id: Option<i64>,
+
+	pub entity: String,
+	pub entity_id: i64,
+	pub action: String,
+	pub user_id: i64,
+
+	pub before: Option<AuditData>,
+	pub after: Option<AuditData>,
+
+	#[serde_as(as = "Option<DisplayFromStr>")]
+	pub req_uuid: Option<Uuid>,
+	#[serde_as(as = "Rfc3339")]
+	pub ctime: OffsetDateTime,
+}
+
+impl HasId for AuditEntry {
+	fn id(&self) -> i64 {
+		self.id
+	}
+}
+
+/// The `before`/`after` row json (jsonb column).
+#[derive(Clone, Debug, Serialize)]
+#[serde(transparent)]
+pub struct AuditData(pub Value);
+
+// Note: Delegates to the `serde_json::Value` sqlx jsonb mapping.
+impl Type<Postgres> for AuditData {
+	fn type_info() -> PgTypeInfo {
+		<Value as Type<Postgres>>::type_info()
+	}
+
+	fn compatible(ty: &PgTypeInfo) -> bool {
+		<Value as Type<Postgres>>::compatible(ty)
+	}
+}
+
+impl Encode<'_, Postgres> for AuditData {
+	fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> sqlx::encode::IsNull {
+		<Value as Encode<Postgres>>::encode_by_ref(&self.0, buf)
+	}
+}
+
+impl<'r> Decode<'r, Postgres> for AuditData {
+	fn decode(
+		value: PgValueRef<'r>,
+	) -> core::result::Result<Self, sqlx::error::BoxDynError> {
+		<Value as Decode<Postgres>>::decode(value).map(AuditData)
+	}
+}
+
+impl SqlxBindable for AuditData {
+	fn bind_query<'q>(
+		&'q self,
+		query: sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>,
+	) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments> {
+		query.bind(self.0.clone())
+	}
+}
+
+#[derive(Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditAction {
+	Create,
+	Update,
+	Delete,
+	Restore,
+	Purge,
+}
+
+impl AuditAction {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			AuditAction::Create => "create",
+			AuditAction::Update => "update",
+			AuditAction::Delete => "delete",
+			AuditAction::Restore => "restore",
+			AuditAction::Purge => "purge",
+		}
+	}
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuditEntryFilter {
+	pub id: Option<OpValsInt64>,
+	pub entity: Option<OpValsString>,
+	pub entity_id: Option<OpValsInt64>,
+	pub action: Option<OpValsString>,
+	pub user_id: Option<OpValsInt64>,
+	pub ctime: Option<OpValsTime>,
+}
+
+impl FilterNodes for AuditEntryFilter {
+	fn filter_nodes(self) -> Vec<FilterNode> {
+		[
+			self.id.into_filter_nodes("id"),
+			self.entity.into_filter_nodes("entity"),
+			self.entity_id.into_filter_nodes("entity_id"),
+			self.action.into_filter_nodes("action"),
+			self.user_id.into_filter_nodes("user_id"),
+			self.ctime.into_filter_nodes("ctime"),
+		]
+		.into_iter()
+		.flatten()
+		.collect()
+	}
+}
+// endregion: --- AuditEntry Types
+
+pub struct AuditBmc;
+
+impl DbBmc for AuditBmc {
+	const TABLE: &'static str = "audit_log";
+	const HAS_TIMESTAMPS: bool = false;
+	const WORKSPACE_SCOPED: bool = true;
+}
+
+impl AuditBmc {
+	/// Record an audit entry (called by `model::base`).
+	pub(crate) async fn record(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		entity: &'static str,
+		entity_id: i64,
+		action: AuditAction,
+		before: Option<Value>,
+		after: Option<Value>,
+	) -> Result<()> {
+		let dbx = mm.dbx();
+
+		let fields = vec![
+			("workspace_id", ctx.workspace_id()).into(),
+			("entity", entity).into(),
+			("entity_id", entity_id).into(),
+			("action", action.as_str()).into(),
+			("user_id", ctx.user_id()).into(),
+			("before", before.map(AuditData)).into(),
+			("after", after.map(AuditData)).into(),
+			("req_uuid", ctx.req_uuid()).into(),
+			("ctime", now_utc()).into(),
+		];
+		let sb = sqlb::insert().table(Self::TABLE).data(fields);
+		dbx.exec(&sb).await?;
+
+		Ok(())
+	}
+
+	pub async fn list_page(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		filter: Option<AuditEntryFilter>,
+		list_options: Option<ListOptions>,
+	) -> Result<Page<AuditEntry>> {
+		base::list_page::<Self, _, _>(ctx, mm, filter, list_options).await
+	}
+}
+
+// region:    --- Support
+
+/// Keep only the properties that changed between the `before` and `after`
+/// json objects (i.e., the update diff).
+pub(crate) fn diff(
+	before: Option<Value>,
+	after: Option<Value>,
+) -> (Option<Value>, Option<Value>) {
+	match (before, after) {
+		(Some(Value::Object(mut before)), Some(Value::Object(mut after))) => {
+			let unchanged: Vec<String> = before
+				.iter()
+				.filter(|(name, val)| after.get(name.as_str()) == Some(val))
+				.map(|(name, _)| name.to_string())
+				.collect();
+			for name in unchanged {
+				before.remove(&name);
+				after.remove(&name);
+			}
+			(Some(Value::Object(before)), Some(Value::Object(after)))
+		}
+		(before, after) => (before, after),
+	}
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::_dev_utils::{self, DEMO_WORKSPACE_ID};
+	use crate::model::task::{TaskBmc, TaskForUpdate};
+	use crate::model::workspace::{WorkspaceBmc, WorkspaceForCreate};
+	use anyhow::Result;
+	use serde_json::json;
+	use serial_test::serial;
+
+	#[serial]
+	#[tokio::test]
+	async fn test_record_on_create_update_delete_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_title = "test_record_on_create_update_delete_ok - task 01";
+		let fx_title_new = "test_record_on_create_update_delete_ok - task 01 - new";
+		let fx_task = _dev_utils::seed_tasks(&ctx, &mm, &[fx_title])
+			.await?
+			.remove(0);
+
+		// -- Exec
+		TaskBmc::update(
+			&ctx,
+			&mm,
+			fx_task.id,
+			TaskForUpdate {
+				title: Some(fx_title_new.to_string()),
+				..Default::default()
+			},
+		)
+		.await?;
+		TaskBmc::delete(&ctx, &mm, fx_task.id).await?;
+
+		// -- Check
+		let filter = AuditEntryFilter {
+			entity: Some(OpValsString {
+				eq: Some("task".to_string()),
+				..Default::default()
+			}),
+			entity_id: Some(OpValsInt64 {
+				eq: Some(fx_task.id),
+				..Default::default()
+			}),
+			..Default::default()
+		};
+		let entries = AuditBmc::list_page(&ctx, &mm, Some(filter), None)
+			.await?
+			.data;
+		let actions: Vec<&str> = entries.iter().map(|e| e.action.as_str()).collect();
+		assert_eq!(actions, ["create", "update", "delete"]);
+		let update = &entries[1];
+		assert_eq!(
+			update.before.as_ref().map(|d| &d.0["title"]),
+			Some(&json!(fx_title))
+		);
+		assert_eq!(
+			update.after.as_ref().map(|d| &d.0["title"]),
+			Some(&json!(fx_title_new))
+		);
+		assert!(
+			update.after.as_ref().map(|d| d.0.get("cid")) == Some(None),
+			"Unchanged properties should not be in the update diff"
+		);
+
+		// -- Clean
+		TaskBmc::purge(&ctx, &mm, fx_task.id).await?;
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_list_scope_other_workspace_empty() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let root_ctx = Ctx::root_ctx();
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_title = "test_list_scope_other_workspace_empty - task 01";
+		let fx_task = _dev_utils::seed_tasks(&ctx, &mm, &[fx_title])
+			.await?
+			.remove(0);
+		let fx_workspace_id = WorkspaceBmc::create(
+			&root_ctx,
+			&mm,
+			WorkspaceForCreate {
+				name: "test_list_scope_other_workspace_empty".to_string(),
+			},
+		)
+		.await?;
+		let fx_ctx_other = Ctx::root_ctx().with_workspace_id(fx_workspace_id);
+		let fx_filter = || AuditEntryFilter {
+			entity_id: Some(OpValsInt64 {
+				eq: Some(fx_task.id),
+				..Default::default()
+			}),
+			..Default::default()
+		};
+
+		// -- Exec
+		let entries = AuditBmc::list_page(&ctx, &mm, Some(fx_filter()), None)
+			.await?
+			.data;
+		let entries_other =
+			AuditBmc::list_page(&fx_ctx_other, &mm, Some(fx_filter()), None)
+				.await?
+				.data;
+
+		// -- Check
+		assert!(!entries.is_empty(), "Should have the task create entry");
+		assert!(entries
+			.iter()
+			.all(|e| e.workspace_id == Some(DEMO_WORKSPACE_ID)));
+		assert!(
+			entries_other.is_empty(),
+			"Should not list the entries of another workspace"
+		);
+
+		// -- Clean
+		TaskBmc::purge(&ctx, &mm, fx_task.id).await?;
+		base::purge::<WorkspaceBmc>(&root_ctx, &mm, fx_workspace_id).await?;
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_diff_ok() {
+		// -- Fixtures
+		let fx_before = json!({"id": 1, "title": "a", "version": 0});
+		let fx_after = json!({"id": 1, "title": "b", "version": 1});
+
+		// -- Exec
+		let (before, after) = diff(Some(fx_before), Some(fx_after));
+
+		// -- Check
+		assert_eq!(before, Some(json!({"title": "a", "version": 0})));
+		assert_eq!(after, Some(json!({"title": "b", "version": 1})));
+	}
+}
+// endregion: --- Tests
@@ -2,15 +2,68 @@
 //! For now, focuses on the "Db Bmcs."
 
 use crate::ctx::Ctx;
+use crate::model::audit::{self, AuditAction, AuditBmc};
+use crate::model::filter::{
+	decode_cursor, decode_offset_cursor, encode_cursor, encode_offset_cursor,
+	FilterNode, FilterNodes, ListOptions, Page, SearchHit,
+};
 use crate::model::{Error, ModelManager, Result};
 use lib_base::time::now_utc;
-use sqlb::HasFields;
+use serde_json::Value;
+use sqlb::{HasFields, Raw, SelectSqlBuilder, SqlBuilder, Whereable};
 use sqlx::postgres::PgRow;
-use sqlx::FromRow;
+use sqlx::{Execute, FromRow};
+use time::OffsetDateTime;
+
+const LIST_LIMIT_DEFAULT: i64 = 1000;
+const LIST_LIMIT_MAX: i64 = 5000;
+
+/// The text search configuration of the `search_tsv` columns (see `search_page`).
+const SEARCH_CONFIG: &str = "english";
 
 pub trait DbBmc {
 	const TABLE: &'static str;
 	const HAS_TIMESTAMPS: bool;
+
+	/// When true, `delete` only sets the `deleted_by`/`deleted_at` columns,
+	/// and `get`/`list`/`count`/`update` ignore the deleted rows
+	/// (see `restore` and `purge`).
+	const SOFT_DELETE: bool = false;
+
+	/// When true, the `version` column is incremented by each `update`,
+	/// which fails with `Error::VersionConflict` if the row version is not the
+	/// expected one (see `update_versioned`).
+	const HAS_VERSION: bool = false;
+
+	/// When true, rows belong to a workspace (`workspace_id` column), which
+	/// `create` sets from the ctx workspace, and to which all the other
+	/// functions are scoped.
+	///
+	/// Note: Only the root ctx without workspace is not scoped.
+	const WORKSPACE_SCOPED: bool = false;
+
+	/// Access policy hook, returning the where clauses restricting the rows
+	/// the ctx may access (e.g., only the ones it created).
+	///
+	/// Applied to every `get`/`list`/`count`/`update`/`delete`/`restore`/`purge`,
+	/// except for the root ctx. Rows not accessible are reported as `EntityNotFound`.
+	///
+	/// Default: no restriction.
+	fn access_filter_nodes(_ctx: &Ctx) -> Vec<FilterNode> {
+		Vec::new()
+	}
+
+	/// The columns never written to the `audit_log` (e.g., secrets).
+	const AUDIT_EXCLUDED_COLUMNS: &'static [&'static str] = &[];
+
+	/// The text columns of the `search_tsv` generated column, from which
+	/// `search_page` builds the snippets (empty when not searchable).
+	const SEARCH_COLUMNS: &'static [&'static str] = &[];
+}
+
+/// Entities with an `id` (i.e., the keyset of the `list_page` cursor).
+pub trait HasId {
+	fn id(&self) -> i64;
 }
 
 pub async fn create<MC, E>(ctx: &Ctx, mm: &ModelManager, data: E) -> Result<i64>
@@ -18,65 +71,256 @@ where
 	MC: DbBmc,
 	E: HasFields,
 {
-	let db = mm.db();
+	create_inner::<MC, E>(ctx, mm, ctx.user_id(), data).await
+}
+
+/// Create the entity on behalf of the `cid` user (e.g., a copy keeping the original
+/// creator), the ctx user being the modifier (`mid`) and the audited user.
+pub async fn create_for<MC, E>(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	cid: i64,
+	data: E,
+) -> Result<i64>
+where
+	MC: DbBmc,
+	E: HasFields,
+{
+	create_inner::<MC, E>(ctx, mm, cid, data).await
+}
+
+async fn create_inner<MC, E>(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	cid: i64,
+	data: E,
+) -> Result<i64>
+where
+	MC: DbBmc,
+	E: HasFields,
+{
+	let mm = mm.begin().await?;
+	let dbx = mm.dbx();
 
 	let mut fields = data.not_none_fields();
+	if MC::WORKSPACE_SCOPED {
+		let workspace_id = ctx
+			.workspace_id()
+			.ok_or(Error::WorkspaceNotInCtx { entity: MC::TABLE })?;
+		fields.push(("workspace_id", workspace_id).into());
+	}
 	if MC::HAS_TIMESTAMPS {
 		let user_id = ctx.user_id();
 		let now = now_utc();
-		fields.push(("cid", user_id).into());
+		fields.push(("cid", cid).into());
 		fields.push(("ctime", now).into());
 		fields.push(("mid", user_id).into());
 		fields.push(("mtime", now).into());
 	}
 
-	let (id,) = sqlb::insert()
+	let sb = sqlb::insert()
 		.table(MC::TABLE)
 		.data(fields)
-		.returning(&["id"])
-		.fetch_one::<_, (i64,)>(db)
+		.returning(&["id"]);
+	let (id,) = dbx.fetch_one::<_, (i64,)>(&sb).await?;
+
+	// -- Audit
+	let after = get_row_json::<MC>(&mm, id).await?;
+	AuditBmc::record(ctx, &mm, MC::TABLE, id, AuditAction::Create, None, after)
 		.await?;
 
+	mm.commit().await?;
+
 	Ok(id)
 }
 
-pub async fn get<MC, E>(_ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<E>
+pub async fn get<MC, E>(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<E>
 where
 	MC: DbBmc,
 	E: for<'r> FromRow<'r, PgRow> + Unpin + Send,
 	E: HasFields,
 {
-	let db = mm.db();
+	let dbx = mm.dbx();
 
-	let entity = sqlb::select()
+	let sb = sqlb::select()
 		.table(MC::TABLE)
 		.columns(E::field_names())
-		.and_where("id", "=", id)
-		.fetch_optional::<_, E>(db)
+		.and_where("id", "=", id);
+	let sb = and_where_visible::<MC, _>(ctx, sb);
+	let entity = dbx
+		.fetch_optional::<_, E>(&sb)
 		.await?
 		.ok_or(Error::EntityNotFound { entity: MC::TABLE, id })?;
 
 	Ok(entity)
 }
 
-pub async fn list<MC, E>(_ctx: &Ctx, mm: &ModelManager) -> Result<Vec<E>>
+pub async fn list<MC, E, F>(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	filter: Option<F>,
+	list_options: Option<ListOptions>,
+) -> Result<Vec<E>>
 where
 	MC: DbBmc,
 	E: for<'r> FromRow<'r, PgRow> + Unpin + Send,
 	E: HasFields,
+	F: FilterNodes,
 {
-	let db = mm.db();
+	let dbx = mm.dbx();
 
-	let entities = sqlb::select()
-		.table(MC::TABLE)
-		.columns(E::field_names())
-		.order_by("id")
-		.fetch_all::<_, E>(db)
-		.await?;
+	let (sb, _) = new_list_select::<MC, E, F>(ctx, filter, list_options, 0)?;
+
+	let entities = dbx.fetch_all::<_, E>(&sb).await?;
 
 	Ok(entities)
 }
 
+/// Same as `list`, but returns a `Page` with the `next_cursor`
+/// when there are more entities and the list is ordered by `id`.
+pub async fn list_page<MC, E, F>(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	filter: Option<F>,
+	list_options: Option<ListOptions>,
+) -> Result<Page<E>>
+where
+	MC: DbBmc,
+	E: for<'r> FromRow<'r, PgRow> + Unpin + Send,
+	E: HasFields + HasId,
+	F: FilterNodes,
+{
+	let dbx = mm.dbx();
+
+	// Note: Fetch one extra entity to know if there is a next page.
+	let (sb, list_spec) = new_list_select::<MC, E, F>(ctx, filter, list_options, 1)?;
+
+	let mut data = dbx.fetch_all::<_, E>(&sb).await?;
+
+	let has_next = data.len() as i64 > list_spec.limit;
+	data.truncate(list_spec.limit as usize);
+
+	let next_cursor = if has_next && list_spec.id_ordered {
+		data.last().map(|e| encode_cursor(e.id()))
+	} else {
+		None
+	};
+
+	Ok(Page { data, next_cursor })
+}
+
+/// Full-text search of the entities matching the filter, ordered by rank
+/// (then `id`), with the `Page.next_cursor` when there are more.
+///
+/// The `search` is in the web search syntax (e.g., `"exact phrase" -excluded or other`),
+/// matched against the `search_tsv` column (`tsvector`, with a GIN index).
+///
+/// Note: `list_options.order_bys` is not supported, and the cursor is offset based.
+pub async fn search_page<MC, E, F>(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	search: &str,
+	filter: Option<F>,
+	list_options: Option<ListOptions>,
+) -> Result<Page<SearchHit<E>>>
+where
+	MC: DbBmc,
+	E: for<'r> FromRow<'r, PgRow> + Unpin + Send,
+	E: HasFields,
+	F: FilterNodes,
+{
+	if MC::SEARCH_COLUMNS.is_empty() {
+		return Err(Error::EntityNotSearchable { entity: MC::TABLE });
+	}
+
+	// -- List Options
+	let ListOptions { limit, offset, order_bys, cursor } =
+		list_options.unwrap_or_default();
+	let limit = list_limit(limit)?;
+	if let Some(order_by) = order_bys.and_then(|o| o.into_iter().next()) {
+		return Err(Error::ListOrderByUnknown(order_by));
+	}
+	let offset = match (cursor, offset) {
+		(Some(_), Some(_)) => return Err(Error::ListCursorWithOffset),
+		(Some(cursor), None) => {
+			decode_offset_cursor(&cursor).ok_or(Error::ListCursorInvalid(cursor))?
+		}
+		(None, offset) => offset.unwrap_or(0).max(0),
+	};
+
+	// -- The visible entities matching the filter (as for `list`).
+	let sb = sqlb::select().table(MC::TABLE).columns(&["id"]);
+	let sb = and_where_visible::<MC, _>(ctx, sb);
+	let sb = apply_filter(sb, filter);
+	let ids_sql = sb.sql();
+
+	// -- Build the ranked select.
+	// Note: The `ids_sql` binds `$1..$n`, so the search is `$n+1`.
+	let vals: Vec<_> = sb.vals().collect();
+	let search_idx = vals.len() + 1;
+	let table = MC::TABLE;
+	let columns: Vec<String> = E::field_names()
+		.iter()
+		.map(|name| format!(r#""{table}"."{name}""#))
+		.collect();
+	let search_text: Vec<String> = MC::SEARCH_COLUMNS
+		.iter()
+		.map(|name| format!(r#""{table}"."{name}""#))
+		.collect();
+	let sql = format!(
+		r#"SELECT {columns},
+		     ts_rank("{table}".search_tsv, query) AS rank,
+		     ts_headline('{SEARCH_CONFIG}', {search_text}, query,
+		       'StartSel=<mark>, StopSel=</mark>, MaxFragments=2') AS snippet
+		   FROM "{table}", websearch_to_tsquery('{SEARCH_CONFIG}', ${search_idx}) AS query
+		   WHERE "{table}".search_tsv @@ query AND "{table}".id IN ({ids_sql})
+		   ORDER BY rank DESC, "{table}".id
+		   LIMIT {} OFFSET {offset}"#,
+		// Note: Fetch one extra entity to know if there is a next page.
+		limit + 1,
+		columns = columns.join(", "),
+		// Note: HTML escaped, so that the `<mark>` tags are the only markup of the snippet.
+		search_text =
+			sql_html_escape(&format!("concat_ws(' ', {})", search_text.join(", "))),
+	);
+
+	let mut query = sqlx::query(&sql);
+	for val in vals {
+		query = val.bind_query(query);
+	}
+	let mut query = query.bind(search);
+	let arguments = query.take_arguments().unwrap_or_default();
+	let query = sqlx::query_as_with(&sql, arguments);
+
+	let mut data: Vec<SearchHit<E>> = mm.dbx().fetch_all_as(query).await?;
+
+	let has_next = data.len() as i64 > limit;
+	data.truncate(limit as usize);
+	let next_cursor = has_next.then(|| encode_offset_cursor(offset + limit));
+
+	Ok(Page { data, next_cursor })
+}
+
+pub async fn count<MC, F>(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	filter: Option<F>,
+) -> Result<i64>
+where
+	MC: DbBmc,
+	F: FilterNodes,
+{
+	let dbx = mm.dbx();
+
+	let sb = sqlb::select().table(MC::TABLE).columns(&["count(*)"]);
+	let sb = and_where_visible::<MC, _>(ctx, sb);
+	let sb = apply_filter(sb, filter);
+
+	let (count,) = dbx.fetch_one::<_, (i64,)>(&sb).await?;
+
+	Ok(count)
+}
+
 pub async fn update<MC, E>(
 	ctx: &Ctx,
 	mm: &ModelManager,
@@ -87,7 +331,293 @@ where
 	MC: DbBmc,
 	E: HasFields,
 {
-	let db = mm.db();
+	update_inner::<MC, E>(ctx, mm, id, None, data).await
+}
+
+/// Same as `update`, but fails with `Error::VersionConflict` if the row version
+/// is not the `expected_version` (only for `MC::HAS_VERSION`).
+pub async fn update_versioned<MC, E>(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	id: i64,
+	expected_version: i64,
+	data: E,
+) -> Result<()>
+where
+	MC: DbBmc,
+	E: HasFields,
+{
+	if !MC::HAS_VERSION {
+		return Err(Error::EntityNotVersioned { entity: MC::TABLE });
+	}
+
+	update_inner::<MC, E>(ctx, mm, id, Some(expected_version), data).await
+}
+
+/// Soft delete the entity if `MC::SOFT_DELETE`, otherwise same as `purge`.
+pub async fn delete<MC>(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()>
+where
+	MC: DbBmc,
+{
+	if !MC::SOFT_DELETE {
+		return purge_inner::<MC>(ctx, mm, id, AuditAction::Delete).await;
+	}
+
+	let mm = mm.begin().await?;
+	let dbx = mm.dbx();
+
+	let before = get_row_json::<MC>(&mm, id).await?;
+
+	let fields = vec![
+		("deleted_by", ctx.user_id()).into(),
+		("deleted_at", now_utc()).into(),
+	];
+	let sb = sqlb::update()
+		.table(MC::TABLE)
+		.data(fields)
+		.and_where("id", "=", id);
+	let sb = and_where_visible::<MC, _>(ctx, sb);
+	let count = dbx.exec(&sb).await?;
+
+	if count == 0 {
+		return Err(Error::EntityNotFound { entity: MC::TABLE, id });
+	}
+
+	AuditBmc::record(ctx, &mm, MC::TABLE, id, AuditAction::Delete, before, None)
+		.await?;
+
+	mm.commit().await?;
+
+	Ok(())
+}
+
+/// Restore a soft deleted entity.
+pub async fn restore<MC>(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()>
+where
+	MC: DbBmc,
+{
+	if !MC::SOFT_DELETE {
+		return Err(Error::EntityNotSoftDeletable { entity: MC::TABLE });
+	}
+
+	let mm = mm.begin().await?;
+	let dbx = mm.dbx();
+
+	let mut fields = vec![
+		("deleted_by", None::<i64>).into(),
+		("deleted_at", None::<OffsetDateTime>).into(),
+	];
+	if MC::HAS_TIMESTAMPS {
+		fields.push(("mid", ctx.user_id()).into());
+		fields.push(("mtime", now_utc()).into());
+	}
+
+	// Note: Only match the soft deleted row.
+	let sb = sqlb::update()
+		.table(MC::TABLE)
+		.data(fields)
+		.and_where("id", "=", id)
+		.and_where("deleted_at", "IS DISTINCT FROM", None::<OffsetDateTime>);
+	let sb = and_where_access::<MC, _>(ctx, sb);
+	let count = dbx.exec(&sb).await?;
+
+	if count == 0 {
+		return Err(Error::EntityNotFound { entity: MC::TABLE, id });
+	}
+
+	let after = get_row_json::<MC>(&mm, id).await?;
+	AuditBmc::record(ctx, &mm, MC::TABLE, id, AuditAction::Restore, None, after)
+		.await?;
+
+	mm.commit().await?;
+
+	Ok(())
+}
+
+/// Permanently delete the entity, soft deleted or not.
+pub async fn purge<MC>(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()>
+where
+	MC: DbBmc,
+{
+	purge_inner::<MC>(ctx, mm, id, AuditAction::Purge).await
+}
+
+async fn purge_inner<MC>(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	id: i64,
+	action: AuditAction,
+) -> Result<()>
+where
+	MC: DbBmc,
+{
+	let mm = mm.begin().await?;
+	let dbx = mm.dbx();
+
+	let before = get_row_json::<MC>(&mm, id).await?;
+
+	let sb = sqlb::delete().table(MC::TABLE).and_where("id", "=", id);
+	let sb = and_where_access::<MC, _>(ctx, sb);
+	let count = dbx.exec(&sb).await?;
+
+	if count == 0 {
+		return Err(Error::EntityNotFound { entity: MC::TABLE, id });
+	}
+
+	AuditBmc::record(ctx, &mm, MC::TABLE, id, action, before, None).await?;
+
+	mm.commit().await?;
+
+	Ok(())
+}
+
+// region:    --- Filter & ListOptions Utils
+
+/// The resolved list options of a list select.
+struct ListSpec {
+	limit: i64,
+	/// True when ordered by `id` (asc or desc), i.e., keyset pageable.
+	id_ordered: bool,
+}
+
+/// Build the list select from the filter and list options.
+/// The `extra_limit` is added to the sql limit (not to `ListSpec.limit`).
+fn new_list_select<'a, MC, E, F>(
+	ctx: &Ctx,
+	filter: Option<F>,
+	list_options: Option<ListOptions>,
+	extra_limit: i64,
+) -> Result<(SelectSqlBuilder<'a>, ListSpec)>
+where
+	MC: DbBmc,
+	E: HasFields,
+	F: FilterNodes,
+{
+	let sb = sqlb::select().table(MC::TABLE).columns(E::field_names());
+	let sb = and_where_visible::<MC, _>(ctx, sb);
+	let sb = apply_filter(sb, filter);
+	let (sb, list_spec) = apply_list_options(sb, list_options, E::field_names())?;
+	let sb = sb.limit(list_spec.limit + extra_limit);
+
+	Ok((sb, list_spec))
+}
+
+fn apply_filter<'a, F>(
+	mut sb: SelectSqlBuilder<'a>,
+	filter: Option<F>,
+) -> SelectSqlBuilder<'a>
+where
+	F: FilterNodes,
+{
+	let nodes = filter.map(|f| f.filter_nodes()).unwrap_or_default();
+	for FilterNode { name, op, val } in nodes {
+		sb = sb.and_where(&name, op, val);
+	}
+
+	sb
+}
+
+/// Apply the list options, except the limit, which is returned in the `ListSpec`.
+fn apply_list_options<'a>(
+	mut sb: SelectSqlBuilder<'a>,
+	list_options: Option<ListOptions>,
+	field_names: &[&str],
+) -> Result<(SelectSqlBuilder<'a>, ListSpec)> {
+	let ListOptions { limit, offset, order_bys, cursor } =
+		list_options.unwrap_or_default();
+
+	// -- Limit
+	let limit = list_limit(limit)?;
+
+	// -- Offset
+	if let Some(offset) = offset {
+		sb = sb.offset(offset.max(0));
+	}
+
+	// -- Order Bys
+	// Note: Must be validated against the entity field names, since sqlb will not
+	//       escape a name containing a `(`.
+	let order_bys = order_bys.unwrap_or_else(|| vec!["id".to_string()]);
+	for order_by in order_bys.iter() {
+		let name = order_by.strip_prefix('!').unwrap_or(order_by);
+		if !field_names.contains(&name) {
+			return Err(Error::ListOrderByUnknown(order_by.to_string()));
+		}
+	}
+	let id_desc = match order_bys.as_slice() {
+		[order_by] if order_by == "id" => Some(false),
+		[order_by] if order_by == "!id" => Some(true),
+		_ => None,
+	};
+	let order_bys: Vec<&str> = order_bys.iter().map(|o| o.as_str()).collect();
+	sb = sb.order_bys(&order_bys);
+
+	// -- Cursor
+	// Note: Keyset pagination on `id`, which is stable while rows get inserted.
+	if let Some(cursor) = cursor {
+		let Some(id_desc) = id_desc else {
+			return Err(Error::ListCursorOrderByNotId);
+		};
+		if offset.is_some() {
+			return Err(Error::ListCursorWithOffset);
+		}
+		let last_id =
+			decode_cursor(&cursor).ok_or(Error::ListCursorInvalid(cursor))?;
+		let op = if id_desc { "<" } else { ">" };
+		sb = sb.and_where("id", op, last_id);
+	}
+
+	let list_spec = ListSpec { limit, id_ordered: id_desc.is_some() };
+
+	Ok((sb, list_spec))
+}
+
+/// The list limit (default when absent), which must not be over the max.
+fn list_limit(limit: Option<i64>) -> Result<i64> {
+	let limit = limit.unwrap_or(LIST_LIMIT_DEFAULT);
+	if limit > LIST_LIMIT_MAX {
+		return Err(Error::ListLimitOverMax { max: LIST_LIMIT_MAX, actual: limit });
+	}
+
+	Ok(limit.max(0))
+}
+
+/// The sql expression HTML escaping the `expr` text (as the `ts_headline` input).
+fn sql_html_escape(expr: &str) -> String {
+	[
+		("&", "&amp;"),
+		("<", "&lt;"),
+		(">", "&gt;"),
+		("\"", "&quot;"),
+		("''", "&#39;"),
+	]
+	.iter()
+	.fold(expr.to_string(), |expr, (from, to)| {
+		format!("replace({expr}, '{from}', '{to}')")
+	})
+}
+
+// endregion: --- Filter & ListOptions Utils
+
+// region:    --- Version Utils
+
+/// Update the entity (incrementing its version when `MC::HAS_VERSION`),
+/// and when `expected_version` is given, only if the row version matches.
+async fn update_inner<MC, E>(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	id: i64,
+	expected_version: Option<i64>,
+	data: E,
+) -> Result<()>
+where
+	MC: DbBmc,
+	E: HasFields,
+{
+	let mm = mm.begin().await?;
+	let dbx = mm.dbx();
+
+	let before = get_row_json::<MC>(&mm, id).await?;
 
 	let mut fields = data.not_none_fields();
 
@@ -98,35 +628,154 @@ where
 		fields.push(("mtime", now).into());
 	}
 
-	let count = sqlb::update()
+	if MC::HAS_VERSION {
+		fields.push(("version", Raw("version + 1")).into());
+	}
+
+	let mut sb = sqlb::update()
 		.table(MC::TABLE)
 		.data(fields)
-		.and_where("id", "=", id)
-		.exec(db)
+		.and_where("id", "=", id);
+	if let Some(version) = expected_version {
+		sb = sb.and_where("version", "=", version);
+	}
+	let sb = and_where_visible::<MC, _>(ctx, sb);
+	let count = dbx.exec(&sb).await?;
+
+	if count > 0 {
+		let after = get_row_json::<MC>(&mm, id).await?;
+		let (before, after) = audit::diff(before, after);
+		AuditBmc::record(
+			ctx,
+			&mm,
+			MC::TABLE,
+			id,
+			AuditAction::Update,
+			before,
+			after,
+		)
 		.await?;
 
-	if count == 0 {
-		Err(Error::EntityNotFound { entity: MC::TABLE, id })
-	} else {
-		Ok(())
+		mm.commit().await?;
+
+		return Ok(());
+	}
+
+	// -- Not updated, either not found or version conflict.
+	let actual_version = match expected_version {
+		Some(_) => get_version::<MC>(ctx, &mm, id).await?,
+		None => None,
+	};
+	match (expected_version, actual_version) {
+		(Some(expected), Some(actual)) => {
+			Err(Error::VersionConflict { entity: MC::TABLE, id, expected, actual })
+		}
+		_ => Err(Error::EntityNotFound { entity: MC::TABLE, id }),
 	}
 }
 
-pub async fn delete<MC>(_ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()>
+/// Get the current version of a `MC::HAS_VERSION` entity (None if not found).
+async fn get_version<MC>(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	id: i64,
+) -> Result<Option<i64>>
 where
 	MC: DbBmc,
 {
-	let db = mm.db();
+	let dbx = mm.dbx();
 
-	let count = sqlb::delete()
+	let sb = sqlb::select()
 		.table(MC::TABLE)
-		.and_where("id", "=", id)
-		.exec(db)
-		.await?;
+		.columns(&["version"])
+		.and_where("id", "=", id);
+	let sb = and_where_visible::<MC, _>(ctx, sb);
+	let version = dbx.fetch_optional::<_, (i64,)>(&sb).await?;
 
-	if count == 0 {
-		Err(Error::EntityNotFound { entity: MC::TABLE, id })
+	Ok(version.map(|(version,)| version))
+}
+
+// endregion: --- Version Utils
+
+// region:    --- Audit Utils
+
+/// Get the row as json for the `audit_log` (None if not found), without the
+/// `MC::AUDIT_EXCLUDED_COLUMNS`.
+///
+/// Note: The row is locked (`FOR UPDATE`) until the end of the transaction,
+///       so that the before/after snapshots are consistent.
+async fn get_row_json<MC>(mm: &ModelManager, id: i64) -> Result<Option<Value>>
+where
+	MC: DbBmc,
+{
+	let dbx = mm.dbx();
+
+	let sql = format!(
+		r#"SELECT to_jsonb(t) FROM "{}" t WHERE id = $1 FOR UPDATE"#,
+		MC::TABLE
+	);
+	let query = sqlx::query_as(&sql).bind(id);
+	let row: Option<(Value,)> = dbx.fetch_optional_as(query).await?;
+
+	Ok(row.map(|(mut row,)| {
+		if let Value::Object(props) = &mut row {
+			for name in MC::AUDIT_EXCLUDED_COLUMNS {
+				props.remove(*name);
+			}
+		}
+		row
+	}))
+}
+
+// endregion: --- Audit Utils
+
+// region:    --- Scope Utils
+
+/// Restrict to the rows visible to the ctx, i.e., accessible and not deleted.
+fn and_where_visible<'a, MC, W>(ctx: &Ctx, sb: W) -> W
+where
+	MC: DbBmc,
+	W: Whereable<'a>,
+{
+	let sb = and_where_access::<MC, _>(ctx, sb);
+	and_where_not_deleted::<MC, _>(sb)
+}
+
+/// Add the workspace scope and the `MC::access_filter_nodes` where clauses
+/// (the latter unless root ctx).
+fn and_where_access<'a, MC, W>(ctx: &Ctx, mut sb: W) -> W
+where
+	MC: DbBmc,
+	W: Whereable<'a>,
+{
+	// Note: A ctx without workspace binds NULL, so that no row matches
+	//       (except for the root ctx, which is then not scoped).
+	if MC::WORKSPACE_SCOPED && !(ctx.is_root() && ctx.workspace_id().is_none()) {
+		sb = sb.and_where("workspace_id", "=", ctx.workspace_id());
+	}
+
+	if ctx.is_root() {
+		return sb;
+	}
+	for FilterNode { name, op, val } in MC::access_filter_nodes(ctx) {
+		sb = sb.and_where(&name, op, val);
+	}
+	sb
+}
+
+/// Add the `deleted_at IS NULL` where clause when `MC::SOFT_DELETE`.
+///
+/// Note: `sqlb` only renders `"column" op $n`, hence the `IS NOT DISTINCT FROM NULL` form.
+fn and_where_not_deleted<'a, MC, W>(sb: W) -> W
+where
+	MC: DbBmc,
+	W: Whereable<'a>,
+{
+	if MC::SOFT_DELETE {
+		sb.and_where("deleted_at", "IS NOT DISTINCT FROM", None::<OffsetDateTime>)
 	} else {
-		Ok(())
+		sb
 	}
 }
+
+// endregion: --- Scope Utils
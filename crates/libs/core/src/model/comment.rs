@@ -0,0 +1,257 @@
+use crate::ctx::Ctx;
+use crate::model::base::{self, DbBmc, HasId};
+use crate::model::filter::{
+	FilterNode, FilterNodes, IntoFilterNodes, ListOptions, OpValsInt64, OpValsTime,
+	Page,
+};
+use crate::model::task::TaskBmc;
+use crate::model::{Error, ModelManager, Result};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use sqlb::Fields;
+use sqlx::FromRow;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+// region:    --- Comment Types
+#[serde_as]
+#[derive(Clone, Fields, FromRow, Debug, Serialize)]
+pub struct Comment {
+	pub id: i64,
+	pub workspace_id: i64,
+	pub task_id: i64,
+
+	pub body: String,
+
+	// -- Timestamps
+	// (`cid` being the comment author)
+	pub cid: i64,
+	#[serde_as(as = "Rfc3339")]
+	pub ctime: OffsetDateTime,
+	pub mid: i64,
+	#[serde_as(as = "Rfc3339")]
+	pub mtime: OffsetDateTime,
+}
+
+impl HasId for Comment {
+	fn id(&self) -> i64 {
+		self.id
+	}
+}
+
+#[derive(Deserialize, Fields)]
+pub struct CommentForCreate {
+	pub task_id: i64,
+	pub body: String,
+}
+
+#[derive(Deserialize, Fields)]
+pub struct CommentForUpdate {
+	pub body: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CommentFilter {
+	pub id: Option<OpValsInt64>,
+	pub task_id: Option<OpValsInt64>,
+
+	pub cid: Option<OpValsInt64>,
+	pub ctime: Option<OpValsTime>,
+	pub mid: Option<OpValsInt64>,
+	pub mtime: Option<OpValsTime>,
+}
+
+impl FilterNodes for CommentFilter {
+	fn filter_nodes(self) -> Vec<FilterNode> {
+		[
+			self.id.into_filter_nodes("id"),
+			self.task_id.into_filter_nodes("task_id"),
+			self.cid.into_filter_nodes("cid"),
+			self.ctime.into_filter_nodes("ctime"),
+			self.mid.into_filter_nodes("mid"),
+			self.mtime.into_filter_nodes("mtime"),
+		]
+		.into_iter()
+		.flatten()
+		.collect()
+	}
+}
+// endregion: --- Comment Types
+
+/// The role that can edit and delete the comments of other authors.
+const ADMIN_ROLE: &str = "admin";
+
+pub struct CommentBmc;
+
+/// Note: No access policy of its own, the comments of a task are accessible
+///       to the ones who can access the task (checked by the `CommentBmc` functions).
+impl DbBmc for CommentBmc {
+	const TABLE: &'static str = "comment";
+	const HAS_TIMESTAMPS: bool = true;
+	const WORKSPACE_SCOPED: bool = true;
+}
+
+impl CommentBmc {
+	pub async fn create(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		comment_c: CommentForCreate,
+	) -> Result<i64> {
+		TaskBmc::get(ctx, mm, comment_c.task_id).await?;
+
+		base::create::<Self, _>(ctx, mm, comment_c).await
+	}
+
+	pub async fn get(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Comment> {
+		let comment: Comment = base::get::<Self, _>(ctx, mm, id).await?;
+		TaskBmc::get(ctx, mm, comment.task_id).await?;
+
+		Ok(comment)
+	}
+
+	/// List the comments of a task (oldest first, by default).
+	pub async fn list_page_by_task(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		task_id: i64,
+		list_options: Option<ListOptions>,
+	) -> Result<Page<Comment>> {
+		TaskBmc::get(ctx, mm, task_id).await?;
+
+		let filter = CommentFilter {
+			task_id: Some(OpValsInt64 { eq: Some(task_id), ..Default::default() }),
+			..Default::default()
+		};
+
+		base::list_page::<Self, _, _>(ctx, mm, Some(filter), list_options).await
+	}
+
+	/// Only the author (or an admin) can update the comment.
+	pub async fn update(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		id: i64,
+		comment_u: CommentForUpdate,
+	) -> Result<()> {
+		let comment = Self::get(ctx, mm, id).await?;
+		check_author_or_admin(ctx, &comment)?;
+
+		base::update::<Self, _>(ctx, mm, id, comment_u).await
+	}
+
+	/// Only the author (or an admin) can delete the comment.
+	pub async fn delete(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+		let comment = Self::get(ctx, mm, id).await?;
+		check_author_or_admin(ctx, &comment)?;
+
+		base::delete::<Self>(ctx, mm, id).await
+	}
+}
+
+fn check_author_or_admin(ctx: &Ctx, comment: &Comment) -> Result<()> {
+	if ctx.is_root() || ctx.user_id() == comment.cid || ctx.has_role(ADMIN_ROLE) {
+		Ok(())
+	} else {
+		Err(Error::CommentNotAuthor { id: comment.id })
+	}
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::_dev_utils::{self, DEMO_WORKSPACE_ID};
+	use crate::model::task::TaskForCreate;
+	use anyhow::Result;
+	use serial_test::serial;
+
+	#[serial]
+	#[tokio::test]
+	async fn test_list_by_task_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_bodies = &["comment 01", "comment 02"];
+		let task_id = TaskBmc::create(
+			&ctx,
+			&mm,
+			TaskForCreate {
+				title: "test_list_by_task_ok - task 01".to_string(),
+				..Default::default()
+			},
+		)
+		.await?;
+		for body in fx_bodies {
+			let comment_c = CommentForCreate { task_id, body: body.to_string() };
+			CommentBmc::create(&ctx, &mm, comment_c).await?;
+		}
+
+		// -- Exec
+		let page = CommentBmc::list_page_by_task(&ctx, &mm, task_id, None).await?;
+
+		// -- Check
+		let bodies: Vec<&str> = page.data.iter().map(|c| c.body.as_str()).collect();
+		assert_eq!(bodies, fx_bodies);
+
+		// -- Clean
+		TaskBmc::purge(&ctx, &mm, task_id).await?;
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_update_err_not_author() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let fx_ctx = Ctx::new(1000)?.with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_admin_ctx = fx_ctx
+			.clone()
+			.with_rbac(vec![ADMIN_ROLE.to_string()], Vec::new());
+		let root_ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let task_id = TaskBmc::create(
+			&fx_ctx,
+			&mm,
+			TaskForCreate {
+				title: "test_update_err_not_author - task 01".to_string(),
+				..Default::default()
+			},
+		)
+		.await?;
+		// Comment of another author (root) on the fx_ctx user task.
+		let comment_id = CommentBmc::create(
+			&root_ctx,
+			&mm,
+			CommentForCreate {
+				task_id,
+				body: "test_update_err_not_author - comment 01".to_string(),
+			},
+		)
+		.await?;
+		let fx_comment_u = || CommentForUpdate {
+			body: "test_update_err_not_author - comment 01 - new".to_string(),
+		};
+
+		// -- Exec
+		let res = CommentBmc::update(&fx_ctx, &mm, comment_id, fx_comment_u()).await;
+		let res_admin =
+			CommentBmc::update(&fx_admin_ctx, &mm, comment_id, fx_comment_u()).await;
+
+		// -- Check
+		assert!(
+			matches!(res, Err(Error::CommentNotAuthor { id }) if id == comment_id),
+			"Non author should not update the comment. Actual: {res:?}"
+		);
+		assert!(
+			res_admin.is_ok(),
+			"Admin should update the comment. Actual: {res_admin:?}"
+		);
+
+		// -- Clean
+		TaskBmc::purge(&root_ctx, &mm, task_id).await?;
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
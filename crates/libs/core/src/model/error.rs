@@ -1,5 +1,7 @@
-use crate::model::store;
+use crate::model::recurrence;
+use crate::model::store::{self, blob, dbx};
 use crate::pwd;
+use lib_base::position;
 use serde::Serialize;
 use serde_with::{serde_as, DisplayFromStr};
 pub type Result<T> = core::result::Result<T, Error>;
@@ -7,12 +9,85 @@ pub type Result<T> = core::result::Result<T, Error>;
 #[serde_as]
 #[derive(Debug, Serialize)]
 pub enum Error {
-	EntityNotFound { entity: &'static str, id: i64 },
-	UserAlreadyExists { username: String },
+	EntityNotFound {
+		entity: &'static str,
+		id: i64,
+	},
+	EntityNotSoftDeletable {
+		entity: &'static str,
+	},
+	EntityNotVersioned {
+		entity: &'static str,
+	},
+	EntityNotSearchable {
+		entity: &'static str,
+	},
+	VersionConflict {
+		entity: &'static str,
+		id: i64,
+		expected: i64,
+		actual: i64,
+	},
+	TaskParentCycle {
+		id: i64,
+		parent_id: i64,
+	},
+	TaskBlockerCycle {
+		id: i64,
+		blocker_id: i64,
+	},
+	TaskAssigneeNotMember {
+		id: i64,
+		assignee_id: i64,
+		workspace_id: i64,
+	},
+	LabelAlreadyExists {
+		name: String,
+	},
+	CommentNotAuthor {
+		id: i64,
+	},
+	AttachmentTooLarge {
+		size: usize,
+		max_size: usize,
+	},
+	AttachmentFileNameInvalid(String),
+	UserAlreadyExists {
+		username: String,
+	},
+	UserUsernameInvalid {
+		username: String,
+	},
+	UserEmailAlreadyExists {
+		email: String,
+	},
+	UserEmailInvalid {
+		email: String,
+	},
+	RoleNotFound {
+		name: String,
+	},
+	WorkspaceNotInCtx {
+		entity: &'static str,
+	},
+
+	// -- List
+	ListLimitOverMax {
+		max: i64,
+		actual: i64,
+	},
+	ListOrderByUnknown(String),
+	ListCursorInvalid(String),
+	ListCursorOrderByNotId,
+	ListCursorWithOffset,
 
 	// -- Modules
 	Pwd(pwd::Error),
+	Position(#[serde_as(as = "DisplayFromStr")] position::Error),
+	Recurrence(recurrence::Error),
 	Store(store::Error),
+	Blob(blob::Error),
+	Dbx(dbx::Error),
 
 	// -- Externals
 	Sqlx(#[serde_as(as = "DisplayFromStr")] sqlx::Error),
@@ -25,12 +100,36 @@ impl From<store::Error> for Error {
 	}
 }
 
+impl From<blob::Error> for Error {
+	fn from(val: blob::Error) -> Self {
+		Self::Blob(val)
+	}
+}
+
+impl From<dbx::Error> for Error {
+	fn from(val: dbx::Error) -> Self {
+		Self::Dbx(val)
+	}
+}
+
 impl From<pwd::Error> for Error {
 	fn from(val: pwd::Error) -> Self {
 		Error::Pwd(val)
 	}
 }
 
+impl From<recurrence::Error> for Error {
+	fn from(val: recurrence::Error) -> Self {
+		Error::Recurrence(val)
+	}
+}
+
+impl From<position::Error> for Error {
+	fn from(val: position::Error) -> Self {
+		Error::Position(val)
+	}
+}
+
 impl From<sqlx::Error> for Error {
 	fn from(val: sqlx::Error) -> Self {
 		Error::Sqlx(val)
@@ -0,0 +1,452 @@
+//! Filter and list options types for the `list` Bmc functions.
+//!
+//! Design:
+//!
+//! - Each entity exposes a typed filter struct (e.g., `TaskFilter`) made of
+//!   `OpVals...` properties, one per filterable column.
+//! - An `OpVals...` holds the operations to apply on a column
+//!   (e.g., `{"$starts_with": "abc", "$not": "abcd"}`), all `AND`ed together.
+//! - The entity filter implements `FilterNodes`, which flattens it into `FilterNode`s
+//!   that `model::base` applies as `sqlb` where clauses.
+//! - `ListOptions` carries the limit, offset, and order_bys (`!` prefix for descending,
+//!   as with `sqlb`).
+//! - `ListOptions.cursor` is the opaque `Page.next_cursor` of a previous `list_page`,
+//!   for keyset pagination on `id` (only for lists ordered by `id` or `!id`).
+//! - The `search_page` results (`SearchHit`s) are ordered by rank, so their cursor is
+//!   offset based (same `ListOptions` and `Page` otherwise).
+//!
+
+use lib_base::b64::{b64u_decode_into_string, b64u_encode};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use sqlb::SqlxBindable;
+use sqlx::FromRow;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+// region:    --- ListOptions
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ListOptions {
+	pub limit: Option<i64>,
+	pub offset: Option<i64>,
+	pub order_bys: Option<Vec<String>>,
+	pub cursor: Option<String>,
+}
+
+// endregion: --- ListOptions
+
+// region:    --- Page
+
+#[derive(Debug, Serialize)]
+pub struct Page<E> {
+	pub data: Vec<E>,
+	pub next_cursor: Option<String>,
+}
+
+/// Cursor string format: b64u of `id:{last_id}`
+pub(crate) fn encode_cursor(last_id: i64) -> String {
+	b64u_encode(&format!("id:{last_id}"))
+}
+
+pub(crate) fn decode_cursor(cursor: &str) -> Option<i64> {
+	b64u_decode_into_string(cursor)
+		.ok()?
+		.strip_prefix("id:")?
+		.parse()
+		.ok()
+}
+
+/// Search cursor string format: b64u of `offset:{next_offset}`
+pub(crate) fn encode_offset_cursor(next_offset: i64) -> String {
+	b64u_encode(&format!("offset:{next_offset}"))
+}
+
+pub(crate) fn decode_offset_cursor(cursor: &str) -> Option<i64> {
+	b64u_decode_into_string(cursor)
+		.ok()?
+		.strip_prefix("offset:")?
+		.parse()
+		.ok()
+}
+
+/// A full-text search result (see `base::search_page`).
+#[derive(Debug, FromRow, Serialize)]
+pub struct SearchHit<E> {
+	#[serde(flatten)]
+	#[sqlx(flatten)]
+	pub entity: E,
+	pub rank: f32,
+	/// The matching fragments of the searched text (HTML escaped),
+	/// the matches in `<mark>` tags.
+	pub snippet: String,
+}
+
+// endregion: --- Page
+
+// region:    --- FilterNode
+
+/// A single `"column" op $n` where clause.
+///
+/// Note: The `name` is always built from a static column name of a filter type,
+///       never from user input, as it might not be escaped by `sqlb`
+///       (e.g., `(ARRAY["id"])` for the `$in` operation).
+#[derive(Debug)]
+pub struct FilterNode {
+	pub name: String,
+	pub op: &'static str,
+	pub val: FilterVal,
+}
+
+impl FilterNode {
+	pub fn new(name: impl Into<String>, op: &'static str, val: FilterVal) -> Self {
+		Self { name: name.into(), op, val }
+	}
+}
+
+/// The bindable value of a `FilterNode`.
+#[derive(Debug, Clone)]
+pub enum FilterVal {
+	Int64(i64),
+	Int64s(Vec<i64>),
+	String(String),
+	Strings(Vec<String>),
+	Time(OffsetDateTime),
+}
+
+impl SqlxBindable for FilterVal {
+	fn bind_query<'q>(
+		&'q self,
+		query: sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>,
+	) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments> {
+		match self {
+			FilterVal::Int64(val) => query.bind(*val),
+			FilterVal::Int64s(vals) => query.bind(vals.clone()),
+			FilterVal::String(val) => query.bind(val.clone()),
+			FilterVal::Strings(vals) => query.bind(vals.clone()),
+			FilterVal::Time(val) => query.bind(*val),
+		}
+	}
+}
+
+/// Implemented by the entity filter types (e.g., `TaskFilter`).
+pub trait FilterNodes {
+	fn filter_nodes(self) -> Vec<FilterNode>;
+}
+
+/// Implemented by the `OpVals...` types, given the column name they apply to.
+pub trait IntoFilterNodes {
+	fn into_filter_nodes(self, name: &str) -> Vec<FilterNode>;
+}
+
+impl<T: IntoFilterNodes> IntoFilterNodes for Option<T> {
+	fn into_filter_nodes(self, name: &str) -> Vec<FilterNode> {
+		self.map(|v| v.into_filter_nodes(name)).unwrap_or_default()
+	}
+}
+
+// endregion: --- FilterNode
+
+// region:    --- OpVals Types
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OpValsInt64 {
+	#[serde(rename = "$eq")]
+	pub eq: Option<i64>,
+	#[serde(rename = "$not")]
+	pub not: Option<i64>,
+	#[serde(rename = "$in")]
+	pub in_: Option<Vec<i64>>,
+	#[serde(rename = "$not_in")]
+	pub not_in: Option<Vec<i64>>,
+	#[serde(rename = "$gt")]
+	pub gt: Option<i64>,
+	#[serde(rename = "$gte")]
+	pub gte: Option<i64>,
+	#[serde(rename = "$lt")]
+	pub lt: Option<i64>,
+	#[serde(rename = "$lte")]
+	pub lte: Option<i64>,
+}
+
+impl IntoFilterNodes for OpValsInt64 {
+	fn into_filter_nodes(self, name: &str) -> Vec<FilterNode> {
+		let Self { eq, not, in_, not_in, gt, gte, lt, lte } = self;
+		let mut nodes = Vec::new();
+
+		let cmps = [
+			("=", eq),
+			("!=", not),
+			(">", gt),
+			(">=", gte),
+			("<", lt),
+			("<=", lte),
+		];
+		for (op, val) in cmps {
+			if let Some(val) = val {
+				nodes.push(FilterNode::new(name, op, FilterVal::Int64(val)));
+			}
+		}
+		if let Some(vals) = in_ {
+			nodes.push(in_node(name, "bigint", false, FilterVal::Int64s(vals)));
+		}
+		if let Some(vals) = not_in {
+			nodes.push(in_node(name, "bigint", true, FilterVal::Int64s(vals)));
+		}
+
+		nodes
+	}
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OpValsString {
+	#[serde(rename = "$eq")]
+	pub eq: Option<String>,
+	#[serde(rename = "$not")]
+	pub not: Option<String>,
+	#[serde(rename = "$in")]
+	pub in_: Option<Vec<String>>,
+	#[serde(rename = "$not_in")]
+	pub not_in: Option<Vec<String>>,
+	#[serde(rename = "$contains")]
+	pub contains: Option<String>,
+	#[serde(rename = "$starts_with")]
+	pub starts_with: Option<String>,
+	#[serde(rename = "$ends_with")]
+	pub ends_with: Option<String>,
+}
+
+impl IntoFilterNodes for OpValsString {
+	fn into_filter_nodes(self, name: &str) -> Vec<FilterNode> {
+		let Self {
+			eq,
+			not,
+			in_,
+			not_in,
+			contains,
+			starts_with,
+			ends_with,
+		} = self;
+		let mut nodes = Vec::new();
+
+		if let Some(val) = eq {
+			nodes.push(FilterNode::new(name, "=", FilterVal::String(val)));
+		}
+		if let Some(val) = not {
+			nodes.push(FilterNode::new(name, "!=", FilterVal::String(val)));
+		}
+		if let Some(vals) = in_ {
+			nodes.push(in_node(name, "text", false, FilterVal::Strings(vals)));
+		}
+		if let Some(vals) = not_in {
+			nodes.push(in_node(name, "text", true, FilterVal::Strings(vals)));
+		}
+		if let Some(val) = contains {
+			let pattern = format!("%{}%", escape_like(&val));
+			nodes.push(FilterNode::new(name, "LIKE", FilterVal::String(pattern)));
+		}
+		if let Some(val) = starts_with {
+			let pattern = format!("{}%", escape_like(&val));
+			nodes.push(FilterNode::new(name, "LIKE", FilterVal::String(pattern)));
+		}
+		if let Some(val) = ends_with {
+			let pattern = format!("%{}", escape_like(&val));
+			nodes.push(FilterNode::new(name, "LIKE", FilterVal::String(pattern)));
+		}
+
+		nodes
+	}
+}
+
+#[serde_as]
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OpValsTime {
+	#[serde_as(as = "Option<Rfc3339>")]
+	#[serde(rename = "$eq", default)]
+	pub eq: Option<OffsetDateTime>,
+	#[serde_as(as = "Option<Rfc3339>")]
+	#[serde(rename = "$gt", default)]
+	pub gt: Option<OffsetDateTime>,
+	#[serde_as(as = "Option<Rfc3339>")]
+	#[serde(rename = "$gte", default)]
+	pub gte: Option<OffsetDateTime>,
+	#[serde_as(as = "Option<Rfc3339>")]
+	#[serde(rename = "$lt", default)]
+	pub lt: Option<OffsetDateTime>,
+	#[serde_as(as = "Option<Rfc3339>")]
+	#[serde(rename = "$lte", default)]
+	pub lte: Option<OffsetDateTime>,
+}
+
+impl IntoFilterNodes for OpValsTime {
+	fn into_filter_nodes(self, name: &str) -> Vec<FilterNode> {
+		let Self { eq, gt, gte, lt, lte } = self;
+
+		[("=", eq), (">", gt), (">=", gte), ("<", lt), ("<=", lte)]
+			.into_iter()
+			.filter_map(|(op, val)| {
+				val.map(|val| FilterNode::new(name, op, FilterVal::Time(val)))
+			})
+			.collect()
+	}
+}
+
+/// For the postgres enum columns, with `E` the Rust enum
+/// (so that unknown values fail at deserialization).
+///
+/// Note: All operations use the `$in` array form (see `in_node`), which compares
+///       the column as text, since `"column" = $n` would need an enum typed value.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OpValsEnum<E> {
+	#[serde(rename = "$eq")]
+	pub eq: Option<E>,
+	#[serde(rename = "$not")]
+	pub not: Option<E>,
+	#[serde(rename = "$in")]
+	pub in_: Option<Vec<E>>,
+	#[serde(rename = "$not_in")]
+	pub not_in: Option<Vec<E>>,
+}
+
+impl<E> Default for OpValsEnum<E> {
+	fn default() -> Self {
+		Self {
+			eq: None,
+			not: None,
+			in_: None,
+			not_in: None,
+		}
+	}
+}
+
+impl<E: AsRef<str>> IntoFilterNodes for OpValsEnum<E> {
+	fn into_filter_nodes(self, name: &str) -> Vec<FilterNode> {
+		let Self { eq, not, in_, not_in } = self;
+		let to_strings =
+			|vals: Vec<E>| vals.iter().map(|v| v.as_ref().to_string()).collect();
+
+		[
+			(false, eq.map(|v| vec![v])),
+			(true, not.map(|v| vec![v])),
+			(false, in_),
+			(true, not_in),
+		]
+		.into_iter()
+		.filter_map(|(negate, vals)| {
+			vals.map(|vals| {
+				in_node(name, "text", negate, FilterVal::Strings(to_strings(vals)))
+			})
+		})
+		.collect()
+	}
+}
+
+/// For the `bigint[]` array columns (or array expressions, e.g., the task label ids).
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OpValsInt64Array {
+	/// The array has at least one of the values.
+	#[serde(rename = "$any")]
+	pub any: Option<Vec<i64>>,
+	/// The array has all of the values.
+	#[serde(rename = "$all")]
+	pub all: Option<Vec<i64>>,
+}
+
+impl IntoFilterNodes for OpValsInt64Array {
+	fn into_filter_nodes(self, name: &str) -> Vec<FilterNode> {
+		let Self { any, all } = self;
+
+		[("&&", any), ("@>", all)]
+			.into_iter()
+			.filter_map(|(op, vals)| {
+				vals.map(|vals| FilterNode::new(name, op, FilterVal::Int64s(vals)))
+			})
+			.collect()
+	}
+}
+
+// endregion: --- OpVals Types
+
+// region:    --- Support
+
+/// Build the `$in` / `$not_in` node.
+///
+/// Note: `sqlb` only renders `"column" op $n`, so `= ANY($n)` cannot be expressed.
+///       Instead, we use the array containment form `(ARRAY["column"::type]) <@ $n`,
+///       which `sqlb` leaves as is because of the `(`.
+fn in_node(name: &str, pg_type: &str, negate: bool, val: FilterVal) -> FilterNode {
+	let not = if negate { "NOT " } else { "" };
+	FilterNode::new(format!(r#"{not}(ARRAY["{name}"::{pg_type}])"#), "<@", val)
+}
+
+/// Escape the `LIKE` special characters (`\` being the Postgres default escape).
+fn escape_like(val: &str) -> String {
+	let mut escaped = String::with_capacity(val.len());
+	for c in val.chars() {
+		if matches!(c, '\\' | '%' | '_') {
+			escaped.push('\\');
+		}
+		escaped.push(c);
+	}
+	escaped
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+	use serde_json::json;
+
+	#[test]
+	fn test_op_vals_string_deserialize_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_json = json!({
+			"$starts_with": "50%_off",
+			"$in": ["a", "b"]
+		});
+
+		// -- Exec
+		let op_vals: OpValsString = serde_json::from_value(fx_json)?;
+		let nodes = op_vals.into_filter_nodes("title");
+
+		// -- Check
+		assert_eq!(nodes.len(), 2);
+		assert_eq!(nodes[0].name, r#"(ARRAY["title"::text])"#);
+		assert_eq!(nodes[0].op, "<@");
+		assert_eq!(nodes[1].op, "LIKE");
+		assert!(
+			matches!(&nodes[1].val, FilterVal::String(s) if s == r"50\%\_off%"),
+			"LIKE pattern not escaped. Actual: {:?}",
+			nodes[1].val
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_op_vals_int64_err_unknown_op() -> Result<()> {
+		// -- Fixtures
+		let fx_json = json!({ "$between": [1, 2] });
+
+		// -- Exec
+		let res = serde_json::from_value::<OpValsInt64>(fx_json);
+
+		// -- Check
+		assert!(
+			res.is_err(),
+			"Should have failed on unknown op. Actual: {res:?}"
+		);
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
@@ -0,0 +1,334 @@
+use crate::ctx::Ctx;
+use crate::model::base::{self, DbBmc, HasId};
+use crate::model::filter::{
+	FilterNode, FilterNodes, IntoFilterNodes, ListOptions, OpValsInt64,
+	OpValsString, OpValsTime, Page,
+};
+use crate::model::store::dbx;
+use crate::model::{Error, ModelManager, Result};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use sqlb::Fields;
+use sqlx::FromRow;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+// region:    --- Label Types
+#[serde_as]
+#[derive(Clone, Fields, FromRow, Debug, Serialize)]
+pub struct Label {
+	pub id: i64,
+	pub workspace_id: i64,
+
+	pub name: String,
+	pub color: Option<String>,
+
+	// -- Timestamps
+	pub cid: i64,
+	#[serde_as(as = "Rfc3339")]
+	pub ctime: OffsetDateTime,
+	pub mid: i64,
+	#[serde_as(as = "Rfc3339")]
+	pub mtime: OffsetDateTime,
+}
+
+impl HasId for Label {
+	fn id(&self) -> i64 {
+		self.id
+	}
+}
+
+#[derive(Default, Deserialize, Fields)]
+pub struct LabelForCreate {
+	pub name: String,
+	pub color: Option<String>,
+}
+
+#[derive(Default, Deserialize, Fields)]
+pub struct LabelForUpdate {
+	pub name: Option<String>,
+	pub color: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LabelFilter {
+	pub id: Option<OpValsInt64>,
+	pub name: Option<OpValsString>,
+
+	pub cid: Option<OpValsInt64>,
+	pub ctime: Option<OpValsTime>,
+	pub mid: Option<OpValsInt64>,
+	pub mtime: Option<OpValsTime>,
+}
+
+impl FilterNodes for LabelFilter {
+	fn filter_nodes(self) -> Vec<FilterNode> {
+		[
+			self.id.into_filter_nodes("id"),
+			self.name.into_filter_nodes("name"),
+			self.cid.into_filter_nodes("cid"),
+			self.ctime.into_filter_nodes("ctime"),
+			self.mid.into_filter_nodes("mid"),
+			self.mtime.into_filter_nodes("mtime"),
+		]
+		.into_iter()
+		.flatten()
+		.collect()
+	}
+}
+// endregion: --- Label Types
+
+pub struct LabelBmc;
+
+/// Note: Labels are shared by all the members of the workspace
+///       (no per-user access policy).
+impl DbBmc for LabelBmc {
+	const TABLE: &'static str = "label";
+	const HAS_TIMESTAMPS: bool = true;
+	const WORKSPACE_SCOPED: bool = true;
+}
+
+impl LabelBmc {
+	pub async fn create(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		label_c: LabelForCreate,
+	) -> Result<i64> {
+		let name = label_c.name.clone();
+		base::create::<Self, _>(ctx, mm, label_c)
+			.await
+			.map_err(|model_error| map_label_already_exists(model_error, name))
+	}
+
+	pub async fn get(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Label> {
+		base::get::<Self, _>(ctx, mm, id).await
+	}
+
+	pub async fn list_page(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		filter: Option<LabelFilter>,
+		list_options: Option<ListOptions>,
+	) -> Result<Page<Label>> {
+		base::list_page::<Self, _, _>(ctx, mm, filter, list_options).await
+	}
+
+	pub async fn update(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		id: i64,
+		label_u: LabelForUpdate,
+	) -> Result<()> {
+		let name = label_u.name.clone();
+		base::update::<Self, _>(ctx, mm, id, label_u)
+			.await
+			.map_err(|model_error| match name {
+				Some(name) => map_label_already_exists(model_error, name),
+				None => model_error,
+			})
+	}
+
+	/// Delete the label (and detach it from its tasks).
+	pub async fn delete(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+		base::delete::<Self>(ctx, mm, id).await
+	}
+}
+
+/// Maps the `(workspace_id, name)` unique violation to `Error::LabelAlreadyExists`.
+fn map_label_already_exists(model_error: Error, name: String) -> Error {
+	match model_error {
+		Error::Dbx(dbx::Error::Sqlx(sqlx_error)) => {
+			if let Some((code, constraint)) = sqlx_error
+				.as_database_error()
+				.and_then(|db_error| db_error.code().zip(db_error.constraint()))
+			{
+				// "23505" => postgresql "unique violation"
+				if code == "23505" && constraint == "label_workspace_id_name_key" {
+					return Error::LabelAlreadyExists { name };
+				}
+			}
+			Error::Dbx(dbx::Error::Sqlx(sqlx_error))
+		}
+		_ => model_error,
+	}
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::_dev_utils::{self, DEMO_WORKSPACE_ID};
+	use crate::model::task::{TaskBmc, TaskFilter, TaskForCreate};
+	use anyhow::Result;
+	use serde_json::json;
+	use serial_test::serial;
+
+	#[serial]
+	#[tokio::test]
+	async fn test_attach_detach_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let label_id = LabelBmc::create(
+			&ctx,
+			&mm,
+			LabelForCreate {
+				name: "test_attach_detach_ok - label 01".to_string(),
+				..Default::default()
+			},
+		)
+		.await?;
+		let task_id = TaskBmc::create(
+			&ctx,
+			&mm,
+			TaskForCreate {
+				title: "test_attach_detach_ok - task 01".to_string(),
+				..Default::default()
+			},
+		)
+		.await?;
+
+		// -- Exec & Check - attach (twice is a no-op)
+		TaskBmc::attach_label(&ctx, &mm, task_id, label_id).await?;
+		TaskBmc::attach_label(&ctx, &mm, task_id, label_id).await?;
+		let task = TaskBmc::get(&ctx, &mm, task_id).await?;
+		assert_eq!(task.label_ids, [label_id]);
+
+		// -- Exec & Check - detach
+		TaskBmc::detach_label(&ctx, &mm, task_id, label_id).await?;
+		let task = TaskBmc::get(&ctx, &mm, task_id).await?;
+		assert!(task.label_ids.is_empty());
+
+		// -- Clean
+		TaskBmc::purge(&ctx, &mm, task_id).await?;
+		LabelBmc::delete(&ctx, &mm, label_id).await?;
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_create_update_err_already_exists() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_name_01 = "test_create_update_err_already_exists - label 01";
+		let fx_name_02 = "test_create_update_err_already_exists - label 02";
+		let label_id_01 = LabelBmc::create(
+			&ctx,
+			&mm,
+			LabelForCreate {
+				name: fx_name_01.to_string(),
+				..Default::default()
+			},
+		)
+		.await?;
+		let label_id_02 = LabelBmc::create(
+			&ctx,
+			&mm,
+			LabelForCreate {
+				name: fx_name_02.to_string(),
+				..Default::default()
+			},
+		)
+		.await?;
+
+		// -- Exec
+		let res_create = LabelBmc::create(
+			&ctx,
+			&mm,
+			LabelForCreate {
+				name: fx_name_01.to_string(),
+				..Default::default()
+			},
+		)
+		.await;
+		let res_update = LabelBmc::update(
+			&ctx,
+			&mm,
+			label_id_02,
+			LabelForUpdate {
+				name: Some(fx_name_01.to_string()),
+				..Default::default()
+			},
+		)
+		.await;
+
+		// -- Check
+		assert!(
+			matches!(&res_create, Err(Error::LabelAlreadyExists { name }) if name == fx_name_01),
+			"res_create not matching expected Error::LabelAlreadyExists. res: {res_create:?}"
+		);
+		assert!(
+			matches!(&res_update, Err(Error::LabelAlreadyExists { name }) if name == fx_name_01),
+			"res_update not matching expected Error::LabelAlreadyExists. res: {res_update:?}"
+		);
+
+		// -- Clean
+		LabelBmc::delete(&ctx, &mm, label_id_01).await?;
+		LabelBmc::delete(&ctx, &mm, label_id_02).await?;
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_filter_tasks_any_all_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_prefix = "test_filter_tasks_any_all_ok";
+		let mut label_ids = Vec::new();
+		for name in ["label 01", "label 02"] {
+			let label_c = LabelForCreate {
+				name: format!("{fx_prefix} - {name}"),
+				..Default::default()
+			};
+			label_ids.push(LabelBmc::create(&ctx, &mm, label_c).await?);
+		}
+		// task 01 has both labels, task 02 only the first one.
+		let mut task_ids = Vec::new();
+		for (title, labels) in
+			[("task 01", &label_ids[..]), ("task 02", &label_ids[..1])]
+		{
+			let task_c = TaskForCreate {
+				title: format!("{fx_prefix} - {title}"),
+				..Default::default()
+			};
+			let task_id = TaskBmc::create(&ctx, &mm, task_c).await?;
+			for label_id in labels {
+				TaskBmc::attach_label(&ctx, &mm, task_id, *label_id).await?;
+			}
+			task_ids.push(task_id);
+		}
+
+		// -- Exec
+		let filter_any: TaskFilter = serde_json::from_value(json!({
+			"label_ids": {"$any": label_ids}
+		}))?;
+		let tasks_any = TaskBmc::list(&ctx, &mm, Some(filter_any), None).await?;
+		let filter_all: TaskFilter = serde_json::from_value(json!({
+			"label_ids": {"$all": label_ids}
+		}))?;
+		let tasks_all = TaskBmc::list(&ctx, &mm, Some(filter_all), None).await?;
+
+		// -- Check
+		let ids_any: Vec<i64> = tasks_any.iter().map(|t| t.id).collect();
+		assert_eq!(ids_any, task_ids);
+		let ids_all: Vec<i64> = tasks_all.iter().map(|t| t.id).collect();
+		assert_eq!(ids_all, task_ids[..1]);
+		assert_eq!(tasks_all[0].label_ids, label_ids);
+
+		// -- Clean
+		for task_id in task_ids {
+			TaskBmc::purge(&ctx, &mm, task_id).await?;
+		}
+		for label_id in label_ids {
+			LabelBmc::delete(&ctx, &mm, label_id).await?;
+		}
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
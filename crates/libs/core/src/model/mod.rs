@@ -11,25 +11,51 @@
 //!   (`Bmc` is short for Backend Model Controller).
 //! - In frameworks like Axum, Tauri, `ModelManager` are typically used as App State.
 //! - ModelManager are designed to be passed as an argument to all Model Controllers functions.
+//! - `ModelManager::begin()` returns a ModelManager bound to a db transaction,
+//!   so that multiple Model Controllers calls commit or rollback atomically (see `store::dbx`).
+//! - The binary contents (e.g., attachments) are in the `ModelManager` blob store
+//!   (see `blob`), and not part of the db transactions. So, in a transaction, the blob
+//!   deletes are deferred until the outermost commit (see `ModelManager::delete_blobs`).
+//! - Db Bmcs scope their queries to the rows the `Ctx` may access with the
+//!   `DbBmc::access_filter_nodes` policy (bypassed by the root ctx), and for the
+//!   `DbBmc::WORKSPACE_SCOPED` ones, to the `Ctx` workspace.
 //!
 
 // region:    --- Modules
 
+pub mod attachment;
+pub mod audit;
 mod base;
+pub mod comment;
 mod error;
+pub mod filter;
+pub mod label;
+pub mod project;
+pub mod recurrence;
+pub mod role;
+pub mod session;
 mod store;
 pub mod task;
 pub mod user;
+pub mod workspace;
 
 pub use self::error::{Error, Result};
+pub use store::blob;
 
-use store::{new_db_pool, Db};
+use blob::BlobStore;
+use std::sync::Arc;
+use store::dbx::Dbx;
+use store::{new_blob_store, new_db_pool};
+use tokio::sync::Mutex;
 
 // endregion: --- Modules
 
 #[derive(Clone)]
 pub struct ModelManager {
-	db: Db,
+	dbx: Dbx,
+	blob_store: Arc<dyn BlobStore>,
+	// Note: Some when in a transaction, shared by the ModelManagers joining it.
+	blob_deletes: Option<Arc<Mutex<Vec<String>>>>,
 }
 
 impl ModelManager {
@@ -37,12 +63,89 @@ impl ModelManager {
 	pub async fn new() -> Result<Self> {
 		let db = new_db_pool().await?;
 
-		Ok(Self { db })
+		Ok(Self {
+			dbx: Dbx::new(db),
+			blob_store: new_blob_store(),
+			blob_deletes: None,
+		})
 	}
 
-	/// Return the sqlx db pool reference.
+	/// Replace the blob store (`LocalBlobStore` on `BLOB_FOLDER` by default).
+	pub fn with_blob_store(mut self, blob_store: Arc<dyn BlobStore>) -> Self {
+		self.blob_store = blob_store;
+		self
+	}
+
+	/// Begin a transaction, returning a new ModelManager bound to it.
+	///
+	/// All Bmc calls given the returned ModelManager (or its clones) run in the transaction,
+	/// which is rolled back if never committed (e.g., on early error return).
+	///
+	/// Note: If `self` is already in a transaction, the returned ModelManager joins it,
+	///       and its `commit()` is a no-op (the outermost one commits).
+	pub async fn begin(&self) -> Result<ModelManager> {
+		let dbx = self.dbx.begin_txn().await?;
+		let blob_deletes = self.blob_deletes.clone().unwrap_or_default();
+
+		Ok(Self {
+			dbx,
+			blob_store: self.blob_store.clone(),
+			blob_deletes: Some(blob_deletes),
+		})
+	}
+
+	/// Commit the transaction, and then delete its deferred blobs
+	/// (no-op if not the outermost one, see `begin()`).
+	pub async fn commit(&self) -> Result<()> {
+		self.dbx.commit_txn().await?;
+
+		if self.dbx.is_txn_owner() {
+			if let Some(blob_deletes) = &self.blob_deletes {
+				let blob_keys = std::mem::take(&mut *blob_deletes.lock().await);
+				for blob_key in blob_keys {
+					self.blob_store.delete(&blob_key).await?;
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Rollback the transaction, and so cancel its deferred blob deletes.
+	pub async fn rollback(&self) -> Result<()> {
+		self.dbx.rollback_txn().await?;
+
+		if let Some(blob_deletes) = &self.blob_deletes {
+			blob_deletes.lock().await.clear();
+		}
+
+		Ok(())
+	}
+
+	/// Delete the blobs of the deleted rows, after the outermost commit if in a transaction
+	/// (so that a rollback does not leave rows without their blobs).
+	pub(crate) async fn delete_blobs(&self, blob_keys: Vec<String>) -> Result<()> {
+		match &self.blob_deletes {
+			Some(blob_deletes) => blob_deletes.lock().await.extend(blob_keys),
+			None => {
+				for blob_key in blob_keys {
+					self.blob_store.delete(&blob_key).await?;
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Return the db executor (on the pool or the transaction).
+	/// (Only for the model layer)
+	pub(crate) fn dbx(&self) -> &Dbx {
+		&self.dbx
+	}
+
+	/// Return the blob store.
 	/// (Only for the model layer)
-	pub(crate) fn db(&self) -> &Db {
-		&self.db
+	pub(crate) fn blob_store(&self) -> &dyn BlobStore {
+		self.blob_store.as_ref()
 	}
 }
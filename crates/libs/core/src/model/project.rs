@@ -0,0 +1,287 @@
+use crate::ctx::Ctx;
+use crate::model::attachment::AttachmentBmc;
+use crate::model::base::{self, DbBmc, HasId};
+use crate::model::filter::{
+	FilterNode, FilterNodes, FilterVal, IntoFilterNodes, ListOptions, OpValsInt64,
+	OpValsString, OpValsTime, Page,
+};
+use crate::model::{ModelManager, Result};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use sqlb::Fields;
+use sqlx::FromRow;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+// region:    --- Project Types
+#[serde_as]
+#[derive(Clone, Fields, FromRow, Debug, Serialize)]
+pub struct Project {
+	pub id: i64,
+	pub workspace_id: i64,
+
+	pub name: String,
+	pub description: Option<String>,
+
+	// -- Timestamps
+	pub cid: i64,
+	#[serde_as(as = "Rfc3339")]
+	pub ctime: OffsetDateTime,
+	pub mid: i64,
+	#[serde_as(as = "Rfc3339")]
+	pub mtime: OffsetDateTime,
+}
+
+impl HasId for Project {
+	fn id(&self) -> i64 {
+		self.id
+	}
+}
+
+#[derive(Default, Deserialize, Fields)]
+pub struct ProjectForCreate {
+	pub name: String,
+	pub description: Option<String>,
+}
+
+#[derive(Default, Deserialize, Fields)]
+pub struct ProjectForUpdate {
+	pub name: Option<String>,
+	#[serde(default, with = "::serde_with::rust::double_option")]
+	pub description: Option<Option<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectFilter {
+	pub id: Option<OpValsInt64>,
+	pub name: Option<OpValsString>,
+
+	pub cid: Option<OpValsInt64>,
+	pub ctime: Option<OpValsTime>,
+	pub mid: Option<OpValsInt64>,
+	pub mtime: Option<OpValsTime>,
+}
+
+impl FilterNodes for ProjectFilter {
+	fn filter_nodes(self) -> Vec<FilterNode> {
+		[
+			self.id.into_filter_nodes("id"),
+			self.name.into_filter_nodes("name"),
+			self.cid.into_filter_nodes("cid"),
+			self.ctime.into_filter_nodes("ctime"),
+			self.mid.into_filter_nodes("mid"),
+			self.mtime.into_filter_nodes("mtime"),
+		]
+		.into_iter()
+		.flatten()
+		.collect()
+	}
+}
+// endregion: --- Project Types
+
+pub struct ProjectBmc;
+
+/// Note: Not soft deleted, so that deleting a project deletes its tasks
+///       (`task.project_id` is `ON DELETE CASCADE`, see `ProjectBmc::delete`).
+impl DbBmc for ProjectBmc {
+	const TABLE: &'static str = "project";
+	const HAS_TIMESTAMPS: bool = true;
+	const WORKSPACE_SCOPED: bool = true;
+
+	/// Only the project creator can access it.
+	fn access_filter_nodes(ctx: &Ctx) -> Vec<FilterNode> {
+		vec![FilterNode::new("cid", "=", FilterVal::Int64(ctx.user_id()))]
+	}
+}
+
+impl ProjectBmc {
+	pub async fn create(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		project_c: ProjectForCreate,
+	) -> Result<i64> {
+		base::create::<Self, _>(ctx, mm, project_c).await
+	}
+
+	pub async fn get(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Project> {
+		base::get::<Self, _>(ctx, mm, id).await
+	}
+
+	pub async fn list_page(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		filter: Option<ProjectFilter>,
+		list_options: Option<ListOptions>,
+	) -> Result<Page<Project>> {
+		base::list_page::<Self, _, _>(ctx, mm, filter, list_options).await
+	}
+
+	pub async fn update(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		id: i64,
+		project_u: ProjectForUpdate,
+	) -> Result<()> {
+		base::update::<Self, _>(ctx, mm, id, project_u).await
+	}
+
+	/// Delete the project and its tasks, with the blobs of their attachments.
+	pub async fn delete(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+		let mm = mm.begin().await?;
+
+		// Note: Listed before, since the attachment rows are deleted by cascade.
+		let blob_keys = AttachmentBmc::list_blob_keys_by_project(&mm, id).await?;
+		base::delete::<Self>(ctx, &mm, id).await?;
+		mm.delete_blobs(blob_keys).await?;
+
+		mm.commit().await?;
+
+		Ok(())
+	}
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::_dev_utils::{self, DEMO_WORKSPACE_ID};
+	use crate::model::attachment::AttachmentForCreate;
+	use crate::model::task::{TaskBmc, TaskFilter, TaskForCreate};
+	use crate::model::Error;
+	use anyhow::Result;
+	use serial_test::serial;
+
+	#[serial]
+	#[tokio::test]
+	async fn test_create_update_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_name = "test_create_update_ok - project 01";
+		let fx_name_new = "test_create_update_ok - project 01 - new";
+
+		// -- Exec
+		let id = ProjectBmc::create(
+			&ctx,
+			&mm,
+			ProjectForCreate {
+				name: fx_name.to_string(),
+				..Default::default()
+			},
+		)
+		.await?;
+		ProjectBmc::update(
+			&ctx,
+			&mm,
+			id,
+			ProjectForUpdate {
+				name: Some(fx_name_new.to_string()),
+				..Default::default()
+			},
+		)
+		.await?;
+
+		// -- Check
+		let project = ProjectBmc::get(&ctx, &mm, id).await?;
+		assert_eq!(project.name, fx_name_new);
+		assert_eq!(project.workspace_id, DEMO_WORKSPACE_ID);
+
+		// -- Clean
+		ProjectBmc::delete(&ctx, &mm, id).await?;
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_update_clear_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_name = "test_update_clear_ok - project 01";
+		let id = ProjectBmc::create(
+			&ctx,
+			&mm,
+			ProjectForCreate {
+				name: fx_name.to_string(),
+				description: Some("some description".to_string()),
+			},
+		)
+		.await?;
+
+		// -- Exec
+		// Absent properties are kept, and null ones cleared.
+		let project_u: ProjectForUpdate =
+			serde_json::from_value(serde_json::json!({
+				"description": null,
+			}))?;
+		ProjectBmc::update(&ctx, &mm, id, project_u).await?;
+
+		// -- Check
+		let project = ProjectBmc::get(&ctx, &mm, id).await?;
+		assert_eq!(project.name, fx_name);
+		assert_eq!(project.description, None);
+
+		// -- Clean
+		ProjectBmc::delete(&ctx, &mm, id).await?;
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_delete_cascade_tasks_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_name = "test_delete_cascade_tasks_ok - project 01";
+		let project_id = ProjectBmc::create(
+			&ctx,
+			&mm,
+			ProjectForCreate {
+				name: fx_name.to_string(),
+				..Default::default()
+			},
+		)
+		.await?;
+		let task_id = TaskBmc::create(
+			&ctx,
+			&mm,
+			TaskForCreate {
+				title: "test_delete_cascade_tasks_ok - task 01".to_string(),
+				project_id: Some(project_id),
+				..Default::default()
+			},
+		)
+		.await?;
+		let fx_filter: TaskFilter = serde_json::from_value(serde_json::json!({
+			"project_id": {"$eq": project_id}
+		}))?;
+		let count = TaskBmc::count(&ctx, &mm, Some(fx_filter)).await?;
+		assert_eq!(count, 1);
+
+		let attachment_c = AttachmentForCreate {
+			task_id,
+			file_name: "file 01.txt".to_string(),
+		};
+		let attachment_id =
+			AttachmentBmc::create(&ctx, &mm, attachment_c, b"file 01").await?;
+		let attachment = AttachmentBmc::get(&ctx, &mm, attachment_id).await?;
+
+		// -- Exec
+		ProjectBmc::delete(&ctx, &mm, project_id).await?;
+
+		// -- Check
+		let res = TaskBmc::get(&ctx, &mm, task_id).await;
+		assert!(
+			matches!(res, Err(Error::EntityNotFound { entity: "task", .. })),
+			"Task should be deleted with its project. Actual: {res:?}"
+		);
+		let res = mm.blob_store().open(&attachment.blob_key).await;
+		assert!(res.is_err(), "Attachment blob should be deleted");
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
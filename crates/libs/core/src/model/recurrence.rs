@@ -0,0 +1,467 @@
+//! Task recurrence rules, a subset of the iCalendar RRULE (RFC 5545).
+//!
+//! Design:
+//!
+//! - Supported parts: `FREQ` (`DAILY`, `WEEKLY`, `MONTHLY`, `YEARLY`, required),
+//!   `INTERVAL` (up to `INTERVAL_MAX`), `BYDAY` (`WEEKLY` only, e.g., `MO,WE`),
+//!   `BYMONTHDAY` (`MONTHLY` and `YEARLY` only, a single day, e.g., `31`), `COUNT`,
+//!   and `UNTIL` (e.g., `20301231T000000Z`),
+//!   e.g., `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR;COUNT=10`.
+//! - Occurrences are computed in UTC, from the previous occurrence `due_at`.
+//! - `COUNT` is the number of occurrences left (including the current one), so it is
+//!   decremented on each next occurrence.
+//! - `MONTHLY` and `YEARLY` keep the `BYMONTHDAY` day of month, clamped to the month end.
+//!   When absent, it is the first occurrence day, and set in the next occurrences rule,
+//!   so that a clamped occurrence does not shift the next ones (e.g., Jan 31, Feb 29, Mar 31).
+//!
+
+use lib_base::time::{add_days, add_months, weekday_index};
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+use time::format_description::FormatItem;
+use time::macros::format_description;
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+const UNTIL_FORMAT: &[FormatItem] =
+	format_description!("[year][month][day]T[hour][minute][second]Z");
+const WEEKDAYS: [&str; 7] = ["MO", "TU", "WE", "TH", "FR", "SA", "SU"];
+
+/// The `INTERVAL` bound, so that the next occurrence stays in the date range.
+pub const INTERVAL_MAX: u32 = 1000;
+
+// region:    --- Recurrence
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Freq {
+	Daily,
+	Weekly,
+	Monthly,
+	Yearly,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recurrence {
+	pub freq: Freq,
+	pub interval: u32,
+	/// The weekdays (from Monday 0), sorted and deduped.
+	pub by_day: Vec<u8>,
+	/// The day of month (1 to 31), clamped to the month end.
+	pub by_month_day: Option<u8>,
+	pub count: Option<u32>,
+	pub until: Option<OffsetDateTime>,
+}
+
+impl Recurrence {
+	/// Returns the next occurrence `due_at` after `due_at`, with the rule for it
+	/// (i.e., `COUNT` decremented), or `None` if the recurrence is over.
+	pub fn next(
+		&self,
+		due_at: OffsetDateTime,
+	) -> Result<Option<(OffsetDateTime, Self)>> {
+		let count = match self.count {
+			Some(count) if count <= 1 => return Ok(None),
+			Some(count) => Some(count - 1),
+			None => None,
+		};
+
+		let interval = i64::from(self.interval);
+		let weeks_days = || interval.checked_mul(7).ok_or(Error::DateOutOfRange);
+		let months =
+			|| i32::try_from(self.interval).map_err(|_| Error::DateOutOfRange);
+		// Note: Anchored on the first occurrence day, since `due_at` might be clamped.
+		let by_month_day = match self.freq {
+			Freq::Monthly | Freq::Yearly => {
+				Some(self.by_month_day.unwrap_or(due_at.day()))
+			}
+			Freq::Daily | Freq::Weekly => None,
+		};
+		let next_due_at = match self.freq {
+			Freq::Daily => add_days(due_at, interval)?,
+			Freq::Weekly if self.by_day.is_empty() => {
+				add_days(due_at, weeks_days()?)?
+			}
+			Freq::Weekly => self.next_by_day(due_at)?,
+			Freq::Monthly => add_months_on_day(due_at, months()?, by_month_day)?,
+			Freq::Yearly => {
+				let months =
+					months()?.checked_mul(12).ok_or(Error::DateOutOfRange)?;
+				add_months_on_day(due_at, months, by_month_day)?
+			}
+		};
+
+		if self.until.is_some_and(|until| next_due_at > until) {
+			return Ok(None);
+		}
+
+		let next = Self { count, by_month_day, ..self.clone() };
+
+		Ok(Some((next_due_at, next)))
+	}
+
+	/// The first day after `due_at` on a `BYDAY` weekday, in a week that is a
+	/// multiple of `INTERVAL` weeks from the `due_at` week (weeks starting on Monday).
+	fn next_by_day(&self, due_at: OffsetDateTime) -> Result<OffsetDateTime> {
+		let interval = i64::from(self.interval);
+		let due_weekday = i64::from(weekday_index(due_at));
+		let weeks_days = interval.checked_mul(7).ok_or(Error::DateOutOfRange)?;
+
+		for days in 1..=weeks_days {
+			let day = add_days(due_at, days)?;
+			let weeks = (due_weekday + days) / 7;
+			if weeks % interval == 0 && self.by_day.contains(&weekday_index(day)) {
+				return Ok(day);
+			}
+		}
+
+		// Note: Not reached, since `by_day` is not empty.
+		add_days(due_at, weeks_days).map_err(Error::from)
+	}
+}
+
+/// Add the months to `due_at`, on the `day` of month (clamped to the month end).
+fn add_months_on_day(
+	due_at: OffsetDateTime,
+	months: i32,
+	day: Option<u8>,
+) -> Result<OffsetDateTime> {
+	let Some(day) = day else {
+		return add_months(due_at, months).map_err(Error::from);
+	};
+
+	let month_start = due_at.replace_day(1).map_err(|_| Error::DateOutOfRange)?;
+	let month_start = add_months(month_start, months)?;
+	let day = day.min(month_start.month().length(month_start.year()));
+
+	month_start
+		.replace_day(day)
+		.map_err(|_| Error::DateOutOfRange)
+}
+
+impl FromStr for Recurrence {
+	type Err = Error;
+
+	fn from_str(rule: &str) -> Result<Self> {
+		let mut freq = None;
+		let mut interval = 1;
+		let mut by_day = Vec::new();
+		let mut by_month_day = None;
+		let mut count = None;
+		let mut until = None;
+
+		let rule = rule.trim();
+		let rule = rule.strip_prefix("RRULE:").unwrap_or(rule);
+		for part in rule.split(';').filter(|p| !p.is_empty()) {
+			let (name, value) = part
+				.split_once('=')
+				.ok_or_else(|| Error::PartInvalid(part.to_string()))?;
+			let value_invalid = || Error::ValueInvalid {
+				name: name.to_string(),
+				value: value.to_string(),
+			};
+
+			match name {
+				"FREQ" => {
+					freq = Some(match value {
+						"DAILY" => Freq::Daily,
+						"WEEKLY" => Freq::Weekly,
+						"MONTHLY" => Freq::Monthly,
+						"YEARLY" => Freq::Yearly,
+						_ => return Err(value_invalid()),
+					})
+				}
+				"INTERVAL" => {
+					interval = value
+						.parse()
+						.ok()
+						.filter(|v| (1..=INTERVAL_MAX).contains(v))
+						.ok_or_else(value_invalid)?
+				}
+				"BYDAY" => {
+					for day in value.split(',') {
+						let day = WEEKDAYS
+							.iter()
+							.position(|d| *d == day)
+							.ok_or_else(value_invalid)?;
+						by_day.push(day as u8);
+					}
+					by_day.sort_unstable();
+					by_day.dedup();
+				}
+				"BYMONTHDAY" => {
+					by_month_day = Some(
+						value
+							.parse()
+							.ok()
+							.filter(|v| (1..=31).contains(v))
+							.ok_or_else(value_invalid)?,
+					)
+				}
+				"COUNT" => {
+					count = Some(
+						value
+							.parse()
+							.ok()
+							.filter(|v| *v > 0)
+							.ok_or_else(value_invalid)?,
+					)
+				}
+				"UNTIL" => {
+					let until_time = PrimitiveDateTime::parse(value, UNTIL_FORMAT)
+						.map_err(|_| value_invalid())?;
+					until = Some(until_time.assume_utc());
+				}
+				_ => return Err(Error::PartNotSupported(name.to_string())),
+			}
+		}
+
+		let freq = freq.ok_or(Error::FreqMissing)?;
+		if !by_day.is_empty() && freq != Freq::Weekly {
+			return Err(Error::ByDayNotWeekly);
+		}
+		if by_month_day.is_some() && !matches!(freq, Freq::Monthly | Freq::Yearly) {
+			return Err(Error::ByMonthDayNotMonthly);
+		}
+
+		Ok(Self {
+			freq,
+			interval,
+			by_day,
+			by_month_day,
+			count,
+			until,
+		})
+	}
+}
+
+/// The normalized rule (e.g., `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR`).
+impl fmt::Display for Recurrence {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let freq = match self.freq {
+			Freq::Daily => "DAILY",
+			Freq::Weekly => "WEEKLY",
+			Freq::Monthly => "MONTHLY",
+			Freq::Yearly => "YEARLY",
+		};
+		write!(f, "FREQ={freq}")?;
+		if self.interval != 1 {
+			write!(f, ";INTERVAL={}", self.interval)?;
+		}
+		if !self.by_day.is_empty() {
+			let days: Vec<&str> =
+				self.by_day.iter().map(|d| WEEKDAYS[*d as usize]).collect();
+			write!(f, ";BYDAY={}", days.join(","))?;
+		}
+		if let Some(by_month_day) = self.by_month_day {
+			write!(f, ";BYMONTHDAY={by_month_day}")?;
+		}
+		if let Some(count) = self.count {
+			write!(f, ";COUNT={count}")?;
+		}
+		if let Some(until) = self.until {
+			let until = until.format(UNTIL_FORMAT).map_err(|_| fmt::Error)?;
+			write!(f, ";UNTIL={until}")?;
+		}
+
+		Ok(())
+	}
+}
+
+// endregion: --- Recurrence
+
+// region:    --- Error
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug, Serialize)]
+pub enum Error {
+	FreqMissing,
+	ByDayNotWeekly,
+	ByMonthDayNotMonthly,
+	PartInvalid(String),
+	PartNotSupported(String),
+	ValueInvalid { name: String, value: String },
+	DateOutOfRange,
+}
+
+// region:    --- Froms
+impl From<lib_base::time::Error> for Error {
+	fn from(_: lib_base::time::Error) -> Self {
+		Self::DateOutOfRange
+	}
+}
+// endregion: --- Froms
+
+// region:    --- Error Boilerplate
+impl core::fmt::Display for Error {
+	fn fmt(
+		&self,
+		fmt: &mut core::fmt::Formatter,
+	) -> core::result::Result<(), core::fmt::Error> {
+		write!(fmt, "{self:?}")
+	}
+}
+
+impl std::error::Error for Error {}
+// endregion: --- Error Boilerplate
+
+// endregion: --- Error
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::{Context, Result};
+	use lib_base::time::{format_time, parse_utc};
+
+	#[test]
+	fn test_parse_display_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_rule =
+			"RRULE:BYDAY=FR,MO,MO;FREQ=WEEKLY;INTERVAL=2;UNTIL=20301231T000000Z";
+
+		// -- Exec
+		let recurrence: Recurrence = fx_rule.parse()?;
+
+		// -- Check
+		assert_eq!(
+			recurrence.to_string(),
+			"FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR;UNTIL=20301231T000000Z"
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_parse_err() -> Result<()> {
+		// -- Exec & Check
+		assert!(matches!(
+			"INTERVAL=2".parse::<Recurrence>(),
+			Err(Error::FreqMissing)
+		));
+		assert!(matches!(
+			"FREQ=HOURLY".parse::<Recurrence>(),
+			Err(Error::ValueInvalid { .. })
+		));
+		assert!(matches!(
+			"FREQ=DAILY;BYMONTH=1".parse::<Recurrence>(),
+			Err(Error::PartNotSupported(_))
+		));
+		assert!(matches!(
+			"FREQ=DAILY;BYDAY=MO".parse::<Recurrence>(),
+			Err(Error::ByDayNotWeekly)
+		));
+		assert!(matches!(
+			"FREQ=WEEKLY;BYMONTHDAY=31".parse::<Recurrence>(),
+			Err(Error::ByMonthDayNotMonthly)
+		));
+		assert!(matches!(
+			"FREQ=MONTHLY;BYMONTHDAY=32".parse::<Recurrence>(),
+			Err(Error::ValueInvalid { .. })
+		));
+		assert!(matches!(
+			"FREQ=DAILY;INTERVAL=10000000".parse::<Recurrence>(),
+			Err(Error::ValueInvalid { .. })
+		));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_next_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		// 2024-01-31 is a Wednesday.
+		let fx_due_at = "2024-01-31T09:00:00Z";
+		let fx_cases = &[
+			("FREQ=DAILY", "2024-02-01T09:00:00Z"),
+			("FREQ=DAILY;INTERVAL=3", "2024-02-03T09:00:00Z"),
+			("FREQ=WEEKLY", "2024-02-07T09:00:00Z"),
+			("FREQ=WEEKLY;BYDAY=MO,FR", "2024-02-02T09:00:00Z"),
+			("FREQ=WEEKLY;BYDAY=MO,WE", "2024-02-05T09:00:00Z"),
+			("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO", "2024-02-12T09:00:00Z"),
+			("FREQ=MONTHLY", "2024-02-29T09:00:00Z"),
+			("FREQ=YEARLY", "2025-01-31T09:00:00Z"),
+		];
+
+		for (rule, expected) in fx_cases {
+			// -- Exec
+			let recurrence: Recurrence = rule.parse()?;
+			let (next_due_at, _) = recurrence
+				.next(parse_utc(fx_due_at)?)?
+				.context("Should have a next occurrence")?;
+
+			// -- Check
+			assert_eq!(format_time(next_due_at), *expected, "rule: {rule}");
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_next_monthly_end_of_month_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_due_at = parse_utc("2024-01-31T09:00:00Z")?;
+		let fx_recurrence: Recurrence = "FREQ=MONTHLY".parse()?;
+
+		// -- Exec
+		let (feb_due_at, feb_recurrence) = fx_recurrence
+			.next(fx_due_at)?
+			.context("Should have a February occurrence")?;
+		let (mar_due_at, mar_recurrence) = feb_recurrence
+			.next(feb_due_at)?
+			.context("Should have a March occurrence")?;
+
+		// -- Check
+		assert_eq!(format_time(feb_due_at), "2024-02-29T09:00:00Z");
+		assert_eq!(feb_recurrence.to_string(), "FREQ=MONTHLY;BYMONTHDAY=31");
+		assert_eq!(format_time(mar_due_at), "2024-03-31T09:00:00Z");
+		assert_eq!(mar_recurrence, feb_recurrence);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_next_count_and_until_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_due_at = parse_utc("2024-01-31T09:00:00Z")?;
+
+		// -- Exec
+		let next = "FREQ=DAILY;COUNT=2"
+			.parse::<Recurrence>()?
+			.next(fx_due_at)?;
+		let last = "FREQ=DAILY;COUNT=1"
+			.parse::<Recurrence>()?
+			.next(fx_due_at)?;
+		let after_until = "FREQ=DAILY;UNTIL=20240201T000000Z"
+			.parse::<Recurrence>()?
+			.next(fx_due_at)?;
+
+		// -- Check
+		let (_, next_recurrence) = next.context("Should have a next occurrence")?;
+		assert_eq!(next_recurrence.to_string(), "FREQ=DAILY;COUNT=1");
+		assert!(last.is_none(), "COUNT=1 should be the last occurrence");
+		assert!(after_until.is_none(), "Should be over after UNTIL");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_next_err_out_of_range() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_due_at = parse_utc("9999-12-01T09:00:00Z")?;
+
+		// -- Exec
+		let res = "FREQ=YEARLY;INTERVAL=1000"
+			.parse::<Recurrence>()?
+			.next(fx_due_at);
+
+		// -- Check
+		assert!(
+			matches!(res, Err(Error::DateOutOfRange)),
+			"Should be out of range. Actual: {res:?}"
+		);
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
@@ -0,0 +1,151 @@
+//! Role-based access control (RBAC) model.
+//!
+//! - A `role` (e.g., `admin`, `member`) has `permission`s (e.g., `task:delete`).
+//! - A user has roles through `user_role`, and so the permissions of its roles.
+//! - The roles and permissions of the request user are resolved into the `Ctx`
+//!   (see `web::mw_auth` in the web-server).
+//!
+
+use crate::ctx::Ctx;
+use crate::model::{Error, ModelManager, Result};
+
+const USER_ROLE_TABLE: &str = "user_role";
+
+pub struct RoleBmc;
+
+impl RoleBmc {
+	/// Give the role (by name) to the user.
+	pub async fn add_to_user(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		user_id: i64,
+		name: &str,
+	) -> Result<()> {
+		let dbx = mm.dbx();
+
+		let query =
+			sqlx::query_as(r#"SELECT id FROM role WHERE name = $1"#).bind(name);
+		let (role_id,): (i64,) = dbx
+			.fetch_optional_as(query)
+			.await?
+			.ok_or_else(|| Error::RoleNotFound { name: name.to_string() })?;
+
+		let sb = sqlb::insert().table(USER_ROLE_TABLE).data(vec![
+			("user_id", user_id).into(),
+			("role_id", role_id).into(),
+		]);
+		dbx.exec(&sb).await?;
+
+		Ok(())
+	}
+
+	pub async fn list_names_by_user(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		user_id: i64,
+	) -> Result<Vec<String>> {
+		let dbx = mm.dbx();
+
+		let query = sqlx::query_as(
+			r#"SELECT role.name FROM role
+			   JOIN user_role ON user_role.role_id = role.id
+			   WHERE user_role.user_id = $1
+			   ORDER BY role.name"#,
+		)
+		.bind(user_id);
+		let names: Vec<(String,)> = dbx.fetch_all_as(query).await?;
+
+		Ok(names.into_iter().map(|(name,)| name).collect())
+	}
+
+	pub async fn list_permissions_by_user(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		user_id: i64,
+	) -> Result<Vec<String>> {
+		let dbx = mm.dbx();
+
+		let query = sqlx::query_as(
+			r#"SELECT DISTINCT permission.name FROM permission
+			   JOIN user_role ON user_role.role_id = permission.role_id
+			   WHERE user_role.user_id = $1
+			   ORDER BY permission.name"#,
+		)
+		.bind(user_id);
+		let names: Vec<(String,)> = dbx.fetch_all_as(query).await?;
+
+		Ok(names.into_iter().map(|(name,)| name).collect())
+	}
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::_dev_utils;
+	use crate::model::base;
+	use crate::model::user::{User, UserBmc, UserForCreate};
+	use anyhow::{Context, Result};
+	use serial_test::serial;
+
+	#[serial]
+	#[tokio::test]
+	async fn test_list_by_user_ok_demo1() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx();
+		let user: User = UserBmc::first_by_username(&ctx, &mm, "demo1")
+			.await?
+			.context("Should have user 'demo1'")?;
+
+		// -- Exec
+		let roles = RoleBmc::list_names_by_user(&ctx, &mm, user.id).await?;
+		let permissions =
+			RoleBmc::list_permissions_by_user(&ctx, &mm, user.id).await?;
+
+		// -- Check
+		assert_eq!(roles, ["member"]);
+		assert!(
+			permissions.iter().any(|p| p == "task:delete"),
+			"demo1 should have 'task:delete'. Actual: {permissions:?}"
+		);
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_add_to_user_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx();
+		let user_id = UserBmc::create(
+			&ctx,
+			&mm,
+			UserForCreate {
+				username: "test_add_to_user_ok-user-01".to_string(),
+				pwd_clear: "welcome-01".to_string(),
+				email: None,
+			},
+		)
+		.await?;
+
+		// -- Exec
+		RoleBmc::add_to_user(&ctx, &mm, user_id, "member").await?;
+		let res = RoleBmc::add_to_user(&ctx, &mm, user_id, "no-such-role").await;
+
+		// -- Check
+		let roles = RoleBmc::list_names_by_user(&ctx, &mm, user_id).await?;
+		assert_eq!(roles, ["member"]);
+		assert!(
+			matches!(res, Err(Error::RoleNotFound { .. })),
+			"Role should exist. Actual: {res:?}"
+		);
+
+		// -- Clean
+		base::delete::<UserBmc>(&ctx, &mm, user_id).await?;
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
@@ -0,0 +1,267 @@
+use crate::config;
+use crate::ctx::Ctx;
+use crate::model::base::{self, DbBmc};
+use crate::model::filter::{FilterNode, FilterVal};
+use crate::model::{ModelManager, Result};
+use lib_base::time::now_utc;
+use serde::Serialize;
+use serde_with::serde_as;
+use sqlb::{Fields, HasFields};
+use sqlx::FromRow;
+use time::format_description::well_known::Rfc3339;
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+
+/// The user agent length bound (in chars, see `session.user_agent`).
+const USER_AGENT_MAX_LEN: usize = 512;
+
+/// The `last_seen_at` precision, so that it is not updated on each request.
+const LAST_SEEN_PRECISION_SEC: i64 = 60;
+
+// region:    --- Session Types
+#[serde_as]
+#[derive(Clone, Fields, FromRow, Debug, Serialize)]
+pub struct Session {
+	pub id: i64,
+	pub user_id: i64,
+
+	/// The web token ident (not exposed, only the token cookie carries it).
+	#[serde(skip)]
+	pub ident: Uuid,
+	pub user_agent: Option<String>,
+	#[serde_as(as = "Rfc3339")]
+	pub last_seen_at: OffsetDateTime,
+
+	// -- Timestamps
+	pub cid: i64,
+	#[serde_as(as = "Rfc3339")]
+	pub ctime: OffsetDateTime,
+	pub mid: i64,
+	#[serde_as(as = "Rfc3339")]
+	pub mtime: OffsetDateTime,
+}
+
+#[derive(Default)]
+pub struct SessionForCreate {
+	pub user_agent: Option<String>,
+}
+
+#[derive(Fields)]
+struct SessionForInsert {
+	user_id: i64,
+	user_agent: Option<String>,
+	last_seen_at: OffsetDateTime,
+}
+// endregion: --- Session Types
+
+pub struct SessionBmc;
+
+impl DbBmc for SessionBmc {
+	const TABLE: &'static str = "session";
+	const HAS_TIMESTAMPS: bool = true;
+
+	/// Only the session user can access it.
+	fn access_filter_nodes(ctx: &Ctx) -> Vec<FilterNode> {
+		vec![FilterNode::new(
+			"user_id",
+			"=",
+			FilterVal::Int64(ctx.user_id()),
+		)]
+	}
+
+	const AUDIT_EXCLUDED_COLUMNS: &'static [&'static str] = &["ident"];
+}
+
+impl SessionBmc {
+	/// Create a session for the ctx user (after deleting their expired ones).
+	pub async fn create(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		session_c: SessionForCreate,
+	) -> Result<i64> {
+		let user_id = ctx.user_id();
+		Self::delete_expired_by_user(ctx, mm, user_id).await?;
+
+		let user_agent = session_c
+			.user_agent
+			.map(|ua| ua.chars().take(USER_AGENT_MAX_LEN).collect());
+		let session_fi = SessionForInsert {
+			user_id,
+			user_agent,
+			last_seen_at: now_utc(),
+		};
+
+		base::create::<Self, _>(ctx, mm, session_fi).await
+	}
+
+	pub async fn get(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Session> {
+		base::get::<Self, _>(ctx, mm, id).await
+	}
+
+	pub async fn first_by_ident(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		ident: Uuid,
+	) -> Result<Option<Session>> {
+		let dbx = mm.dbx();
+
+		let sb = sqlb::select()
+			.table(Self::TABLE)
+			.columns(Session::field_names())
+			.and_where("ident", "=", ident);
+		let session = dbx.fetch_optional::<_, Session>(&sb).await?;
+
+		Ok(session)
+	}
+
+	/// List the ctx user sessions, the last seen first.
+	pub async fn list(ctx: &Ctx, mm: &ModelManager) -> Result<Vec<Session>> {
+		let dbx = mm.dbx();
+
+		let sb = sqlb::select()
+			.table(Self::TABLE)
+			.columns(Session::field_names())
+			.and_where("user_id", "=", ctx.user_id())
+			.order_bys(&["!last_seen_at", "!id"]);
+		let sessions = dbx.fetch_all::<_, Session>(&sb).await?;
+
+		Ok(sessions)
+	}
+
+	/// Update the session `last_seen_at` (only if older than `LAST_SEEN_PRECISION_SEC`).
+	pub async fn update_last_seen(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		session: &Session,
+	) -> Result<()> {
+		let dbx = mm.dbx();
+
+		let now = now_utc();
+		if now - session.last_seen_at < Duration::seconds(LAST_SEEN_PRECISION_SEC) {
+			return Ok(());
+		}
+
+		let sb = sqlb::update()
+			.table(Self::TABLE)
+			.and_where("id", "=", session.id)
+			.data(vec![("last_seen_at", now).into()]);
+		dbx.exec(&sb).await?;
+
+		Ok(())
+	}
+
+	/// Revoke the session (its token stops validating).
+	pub async fn delete(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+		base::delete::<Self>(ctx, mm, id).await
+	}
+
+	/// Delete all the user sessions, but the `except_id` one (if any).
+	pub async fn delete_by_user(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		user_id: i64,
+		except_id: Option<i64>,
+	) -> Result<()> {
+		let dbx = mm.dbx();
+
+		let mut sb = sqlb::delete()
+			.table(Self::TABLE)
+			.and_where("user_id", "=", user_id);
+		if let Some(except_id) = except_id {
+			sb = sb.and_where("id", "!=", except_id);
+		}
+		dbx.exec(&sb).await?;
+
+		Ok(())
+	}
+
+	/// Note: A session token expires `TOKEN_DURATION_SEC` after its last refresh,
+	///       i.e., after the session was last seen.
+	async fn delete_expired_by_user(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		user_id: i64,
+	) -> Result<()> {
+		let dbx = mm.dbx();
+
+		let expired_at = now_utc()
+			- Duration::seconds_f64(config().TOKEN_DURATION_SEC)
+			- Duration::seconds(LAST_SEEN_PRECISION_SEC);
+		let sb = sqlb::delete()
+			.table(Self::TABLE)
+			.and_where("user_id", "=", user_id)
+			.and_where("last_seen_at", "<", expired_at);
+		dbx.exec(&sb).await?;
+
+		Ok(())
+	}
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::_dev_utils;
+	use crate::model::Error;
+	use anyhow::{Context, Result};
+	use serial_test::serial;
+
+	#[serial]
+	#[tokio::test]
+	async fn test_create_list_delete_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::new(1000)?; // demo1
+		let fx_user_agent = "fx-user-agent-01";
+
+		// -- Exec
+		let id = SessionBmc::create(
+			&ctx,
+			&mm,
+			SessionForCreate {
+				user_agent: Some(fx_user_agent.to_string()),
+			},
+		)
+		.await?;
+		let sessions = SessionBmc::list(&ctx, &mm).await?;
+		SessionBmc::delete(&ctx, &mm, id).await?;
+
+		// -- Check
+		let session = sessions
+			.iter()
+			.find(|s| s.id == id)
+			.context("Should have the created session")?;
+		assert_eq!(session.user_agent.as_deref(), Some(fx_user_agent));
+		let session_ident =
+			SessionBmc::first_by_ident(&ctx, &mm, session.ident).await?;
+		assert!(session_ident.is_none(), "Session should be deleted");
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_delete_err_other_user() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let fx_ctx_demo1 = Ctx::new(1000)?;
+		let fx_ctx_other = Ctx::new(1001)?;
+		let id = SessionBmc::create(&fx_ctx_demo1, &mm, SessionForCreate::default())
+			.await?;
+
+		// -- Exec
+		let res = SessionBmc::delete(&fx_ctx_other, &mm, id).await;
+
+		// -- Check
+		assert!(
+			matches!(res, Err(Error::EntityNotFound { entity: "session", .. })),
+			"Should not delete another user session. Actual: {res:?}"
+		);
+
+		// -- Clean
+		SessionBmc::delete_by_user(&fx_ctx_demo1, &mm, 1000, None).await?;
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
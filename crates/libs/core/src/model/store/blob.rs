@@ -0,0 +1,145 @@
+//! `BlobStore` - The model blob storage (e.g., attachment contents).
+//!
+//! Design:
+//!
+//! - The `BlobStore` trait is the pluggable storage of the binary contents, which are kept
+//!   out of the db (the db only stores their keys and metadata).
+//! - The `ModelManager` holds the `BlobStore` (`LocalBlobStore` by default,
+//!   see `ModelManager::with_blob_store` for other implementations, e.g., S3).
+//! - Blobs are immutable. They are written once by key (generated by the model layer),
+//!   then read (streamed) or deleted.
+//! - Blob operations are not part of the db transactions. So, the model layer writes the blob
+//!   before its db row, and deletes it after the commit (an orphan blob being preferable
+//!   to a missing one, see `ModelManager::delete_blobs`).
+//!
+
+// region:    --- Modules
+
+use async_trait::async_trait;
+use serde::Serialize;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::pin::Pin;
+use tokio::fs;
+use tokio::io::AsyncRead;
+
+// endregion: --- Modules
+
+// region:    --- BlobStore
+
+/// The blob content reader, to be streamed.
+pub type BlobReader = Pin<Box<dyn AsyncRead + Send>>;
+
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+	async fn put(&self, key: &str, content: &[u8]) -> Result<()>;
+
+	async fn open(&self, key: &str) -> Result<BlobReader>;
+
+	/// Note: No-op if the blob does not exist.
+	async fn delete(&self, key: &str) -> Result<()>;
+}
+
+// endregion: --- BlobStore
+
+// region:    --- LocalBlobStore
+
+/// Blob store on the local file system, one file per blob in `dir`.
+pub struct LocalBlobStore {
+	dir: PathBuf,
+}
+
+impl LocalBlobStore {
+	pub fn new(dir: impl Into<PathBuf>) -> Self {
+		Self { dir: dir.into() }
+	}
+
+	/// Note: Keys are restricted to `[A-Za-z0-9_-]`, so that they cannot escape `dir`.
+	fn path(&self, key: &str) -> Result<PathBuf> {
+		let valid = !key.is_empty()
+			&& key
+				.chars()
+				.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+		if valid {
+			Ok(self.dir.join(key))
+		} else {
+			Err(Error::KeyInvalid(key.to_string()))
+		}
+	}
+}
+
+#[async_trait]
+impl BlobStore for LocalBlobStore {
+	async fn put(&self, key: &str, content: &[u8]) -> Result<()> {
+		let path = self.path(key)?;
+		let io_err = |ex: std::io::Error| Error::Io {
+			key: key.to_string(),
+			cause: ex.to_string(),
+		};
+
+		// Note: Written to a temporary file first, so that a blob is never partially read.
+		let tmp_path = path.with_extension("tmp");
+		fs::create_dir_all(&self.dir).await.map_err(io_err)?;
+		fs::write(&tmp_path, content).await.map_err(io_err)?;
+		fs::rename(&tmp_path, &path).await.map_err(io_err)?;
+
+		Ok(())
+	}
+
+	async fn open(&self, key: &str) -> Result<BlobReader> {
+		let path = self.path(key)?;
+
+		match fs::File::open(&path).await {
+			Ok(file) => Ok(Box::pin(file)),
+			Err(ex) if ex.kind() == ErrorKind::NotFound => {
+				Err(Error::BlobNotFound(key.to_string()))
+			}
+			Err(ex) => Err(Error::Io {
+				key: key.to_string(),
+				cause: ex.to_string(),
+			}),
+		}
+	}
+
+	async fn delete(&self, key: &str) -> Result<()> {
+		let path = self.path(key)?;
+
+		match fs::remove_file(&path).await {
+			Ok(()) => Ok(()),
+			Err(ex) if ex.kind() == ErrorKind::NotFound => Ok(()),
+			Err(ex) => Err(Error::Io {
+				key: key.to_string(),
+				cause: ex.to_string(),
+			}),
+		}
+	}
+}
+
+// endregion: --- LocalBlobStore
+
+// region:    --- Error
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug, Serialize)]
+pub enum Error {
+	KeyInvalid(String),
+	BlobNotFound(String),
+	Io { key: String, cause: String },
+}
+
+// region:    --- Error Boilerplate
+impl core::fmt::Display for Error {
+	fn fmt(
+		&self,
+		fmt: &mut core::fmt::Formatter,
+	) -> core::result::Result<(), core::fmt::Error> {
+		write!(fmt, "{self:?}")
+	}
+}
+
+impl std::error::Error for Error {}
+// endregion: --- Error Boilerplate
+
+// endregion: --- Error
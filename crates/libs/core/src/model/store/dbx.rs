@@ -0,0 +1,282 @@
+//! `Dbx` - The model db executor.
+//!
+//! Design:
+//!
+//! - All model db access goes through a `Dbx`, which executes the `sqlb` builders
+//!   on the db pool, or on the transaction it is bound to.
+//! - A `Dbx` bound to a transaction is created with `begin_txn()`. Its clones share the same
+//!   transaction, which is committed with `commit_txn()` or rolled back with `rollback_txn()`
+//!   (or when dropped without commit).
+//! - Calling `begin_txn()` on a `Dbx` already bound to a transaction joins it. In this case,
+//!   `commit_txn()` is a no-op, since only the outermost `Dbx` (the owner) commits.
+//!
+
+// region:    --- Modules
+
+use crate::model::store::Db;
+use serde::Serialize;
+use serde_with::{serde_as, DisplayFromStr};
+use sqlb::SqlBuilder;
+use sqlx::postgres::{PgArguments, PgRow};
+use sqlx::query::{Query, QueryAs};
+use sqlx::{FromRow, Postgres, Transaction};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+// endregion: --- Modules
+
+// region:    --- Dbx
+
+#[derive(Clone)]
+pub struct Dbx {
+	db_pool: Db,
+	txn: Option<TxnRef>,
+}
+
+#[derive(Clone)]
+struct TxnRef {
+	// Note: None once committed or rolled back.
+	holder: Arc<Mutex<Option<Transaction<'static, Postgres>>>>,
+	owner: bool,
+}
+
+// Constructor.
+impl Dbx {
+	pub fn new(db_pool: Db) -> Self {
+		Self { db_pool, txn: None }
+	}
+}
+
+// Transaction.
+impl Dbx {
+	pub async fn begin_txn(&self) -> Result<Dbx> {
+		let txn = match &self.txn {
+			Some(txn) => TxnRef { holder: txn.holder.clone(), owner: false },
+			None => {
+				let txn = self.db_pool.begin().await?;
+				TxnRef {
+					holder: Arc::new(Mutex::new(Some(txn))),
+					owner: true,
+				}
+			}
+		};
+
+		Ok(Dbx {
+			db_pool: self.db_pool.clone(),
+			txn: Some(txn),
+		})
+	}
+
+	pub async fn commit_txn(&self) -> Result<()> {
+		let txn = self.txn.as_ref().ok_or(Error::TxnNotActive)?;
+		if !txn.owner {
+			return Ok(());
+		}
+
+		let txn = txn.holder.lock().await.take().ok_or(Error::TxnNotActive)?;
+		txn.commit().await?;
+
+		Ok(())
+	}
+
+	/// True if bound to a transaction it began (and so commits).
+	pub fn is_txn_owner(&self) -> bool {
+		self.txn.as_ref().is_some_and(|txn| txn.owner)
+	}
+
+	/// Rollback the whole transaction, even if not the owner.
+	pub async fn rollback_txn(&self) -> Result<()> {
+		let txn = self.txn.as_ref().ok_or(Error::TxnNotActive)?;
+
+		if let Some(txn) = txn.holder.lock().await.take() {
+			txn.rollback().await?;
+		}
+
+		Ok(())
+	}
+}
+
+// Executors.
+impl Dbx {
+	pub async fn exec<'a, SB>(&self, sb: &'a SB) -> Result<u64>
+	where
+		SB: SqlBuilder<'a> + Sync,
+	{
+		let count = match &self.txn {
+			Some(txn) => {
+				let mut holder = txn.holder.lock().await;
+				let txn = holder.as_mut().ok_or(Error::TxnNotActive)?;
+				sb.exec(&mut **txn).await?
+			}
+			None => sb.exec(&self.db_pool).await?,
+		};
+
+		Ok(count)
+	}
+
+	pub async fn fetch_one<'a, SB, D>(&self, sb: &'a SB) -> Result<D>
+	where
+		SB: SqlBuilder<'a> + Sync,
+		D: for<'r> FromRow<'r, PgRow> + Unpin + Send,
+	{
+		let entity = match &self.txn {
+			Some(txn) => {
+				let mut holder = txn.holder.lock().await;
+				let txn = holder.as_mut().ok_or(Error::TxnNotActive)?;
+				sb.fetch_one::<_, D>(&mut **txn).await?
+			}
+			None => sb.fetch_one::<_, D>(&self.db_pool).await?,
+		};
+
+		Ok(entity)
+	}
+
+	pub async fn fetch_optional<'a, SB, D>(&self, sb: &'a SB) -> Result<Option<D>>
+	where
+		SB: SqlBuilder<'a> + Sync,
+		D: for<'r> FromRow<'r, PgRow> + Unpin + Send,
+	{
+		let entity = match &self.txn {
+			Some(txn) => {
+				let mut holder = txn.holder.lock().await;
+				let txn = holder.as_mut().ok_or(Error::TxnNotActive)?;
+				sb.fetch_optional::<_, D>(&mut **txn).await?
+			}
+			None => sb.fetch_optional::<_, D>(&self.db_pool).await?,
+		};
+
+		Ok(entity)
+	}
+
+	pub async fn fetch_all<'a, SB, D>(&self, sb: &'a SB) -> Result<Vec<D>>
+	where
+		SB: SqlBuilder<'a> + Sync,
+		D: for<'r> FromRow<'r, PgRow> + Unpin + Send,
+	{
+		let entities = match &self.txn {
+			Some(txn) => {
+				let mut holder = txn.holder.lock().await;
+				let txn = holder.as_mut().ok_or(Error::TxnNotActive)?;
+				sb.fetch_all::<_, D>(&mut **txn).await?
+			}
+			None => sb.fetch_all::<_, D>(&self.db_pool).await?,
+		};
+
+		Ok(entities)
+	}
+}
+
+// Raw Executors.
+// Note: For the queries `sqlb` cannot express (e.g., joins).
+impl Dbx {
+	pub async fn exec_raw<'q>(
+		&self,
+		query: Query<'q, Postgres, PgArguments>,
+	) -> Result<u64> {
+		let result = match &self.txn {
+			Some(txn) => {
+				let mut holder = txn.holder.lock().await;
+				let txn = holder.as_mut().ok_or(Error::TxnNotActive)?;
+				query.execute(&mut **txn).await?
+			}
+			None => query.execute(&self.db_pool).await?,
+		};
+
+		Ok(result.rows_affected())
+	}
+
+	pub async fn fetch_one_as<'q, D>(
+		&self,
+		query: QueryAs<'q, Postgres, D, PgArguments>,
+	) -> Result<D>
+	where
+		D: for<'r> FromRow<'r, PgRow> + Unpin + Send,
+	{
+		let entity = match &self.txn {
+			Some(txn) => {
+				let mut holder = txn.holder.lock().await;
+				let txn = holder.as_mut().ok_or(Error::TxnNotActive)?;
+				query.fetch_one(&mut **txn).await?
+			}
+			None => query.fetch_one(&self.db_pool).await?,
+		};
+
+		Ok(entity)
+	}
+
+	pub async fn fetch_optional_as<'q, D>(
+		&self,
+		query: QueryAs<'q, Postgres, D, PgArguments>,
+	) -> Result<Option<D>>
+	where
+		D: for<'r> FromRow<'r, PgRow> + Unpin + Send,
+	{
+		let entity = match &self.txn {
+			Some(txn) => {
+				let mut holder = txn.holder.lock().await;
+				let txn = holder.as_mut().ok_or(Error::TxnNotActive)?;
+				query.fetch_optional(&mut **txn).await?
+			}
+			None => query.fetch_optional(&self.db_pool).await?,
+		};
+
+		Ok(entity)
+	}
+
+	pub async fn fetch_all_as<'q, D>(
+		&self,
+		query: QueryAs<'q, Postgres, D, PgArguments>,
+	) -> Result<Vec<D>>
+	where
+		D: for<'r> FromRow<'r, PgRow> + Unpin + Send,
+	{
+		let entities = match &self.txn {
+			Some(txn) => {
+				let mut holder = txn.holder.lock().await;
+				let txn = holder.as_mut().ok_or(Error::TxnNotActive)?;
+				query.fetch_all(&mut **txn).await?
+			}
+			None => query.fetch_all(&self.db_pool).await?,
+		};
+
+		Ok(entities)
+	}
+}
+
+// endregion: --- Dbx
+
+// region:    --- Error
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+pub enum Error {
+	TxnNotActive,
+
+	// -- Externals
+	Sqlx(#[serde_as(as = "DisplayFromStr")] sqlx::Error),
+}
+
+// region:    --- Froms
+impl From<sqlx::Error> for Error {
+	fn from(val: sqlx::Error) -> Self {
+		Self::Sqlx(val)
+	}
+}
+// endregion: --- Froms
+
+// region:    --- Error Boilerplate
+impl core::fmt::Display for Error {
+	fn fmt(
+		&self,
+		fmt: &mut core::fmt::Formatter,
+	) -> core::result::Result<(), core::fmt::Error> {
+		write!(fmt, "{self:?}")
+	}
+}
+
+impl std::error::Error for Error {}
+// endregion: --- Error Boilerplate
+
+// endregion: --- Error
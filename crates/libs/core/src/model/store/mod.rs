@@ -1,12 +1,16 @@
 // region:    --- Modules
 
+pub mod blob;
+pub mod dbx;
 mod error;
 
 pub use self::error::{Error, Result};
 
 use crate::config;
+use blob::{BlobStore, LocalBlobStore};
 use sqlx::postgres::PgPoolOptions;
 use sqlx::{Pool, Postgres};
+use std::sync::Arc;
 
 // endregion: --- Modules
 
@@ -23,3 +27,11 @@ pub async fn new_db_pool() -> Result<Db> {
 }
 
 // endregion: --- Db Store
+
+// region:    --- Blob Store
+
+pub fn new_blob_store() -> Arc<dyn BlobStore> {
+	Arc::new(LocalBlobStore::new(&config().BLOB_FOLDER))
+}
+
+// endregion: --- Blob Store
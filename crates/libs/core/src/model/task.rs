@@ -1,10 +1,24 @@
 use crate::ctx::Ctx;
-use crate::model::base::DbBmc;
-use crate::model::{base, ModelManager, Result};
-use serde::{Deserialize, Serialize};
-use serde_with::serde_as;
-use sqlb::Fields;
-use sqlx::FromRow;
+use crate::model::attachment::AttachmentBmc;
+use crate::model::base::{DbBmc, HasId};
+use crate::model::filter::{
+	FilterNode, FilterNodes, FilterVal, IntoFilterNodes, ListOptions, OpValsEnum,
+	OpValsInt64, OpValsInt64Array, OpValsString, OpValsTime, Page, SearchHit,
+};
+use crate::model::label::LabelBmc;
+use crate::model::project::ProjectBmc;
+use crate::model::recurrence::Recurrence;
+use crate::model::user::{User, UserBmc};
+use crate::model::workspace::WorkspaceBmc;
+use crate::model::{base, Error, ModelManager, Result};
+use lib_base::position::key_between;
+use lib_base::time::now_utc;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_with::{serde_as, As};
+use sqlb::{Field, Fields, HasFields, SqlxBindable};
+use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueRef};
+use sqlx::{Decode, Encode, FromRow, Postgres, Type};
+use std::borrow::BorrowMut;
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 
@@ -13,8 +27,39 @@ use time::OffsetDateTime;
 #[derive(Clone, Fields, FromRow, Debug, Serialize)]
 pub struct Task {
 	pub id: i64,
+	pub workspace_id: i64,
+	pub project_id: Option<i64>,
+	/// The parent task (for subtasks).
+	pub parent_id: Option<i64>,
+	pub assignee_id: Option<i64>,
 
 	pub title: String,
+	pub description: Option<String>,
+	pub status: TaskStatus,
+	pub priority: i32,
+	#[serde_as(as = "Option<Rfc3339>")]
+	pub due_at: Option<OffsetDateTime>,
+	/// The recurrence rule (see `model::recurrence`), moved to the next occurrence
+	/// when the task is done.
+	pub recurrence: Option<String>,
+	/// The manual ordering key, in the workspace (see `lib_base::position`).
+	/// (list with the `"position"` order_by)
+	pub position: String,
+	/// Set when the status becomes `done` (db trigger).
+	#[serde_as(as = "Option<Rfc3339>")]
+	pub done_at: Option<OffsetDateTime>,
+
+	pub version: i64,
+
+	/// The attached label ids (from `task_label`, loaded by the `TaskBmc` get/list).
+	#[field(skip)]
+	#[sqlx(default)]
+	pub label_ids: Vec<i64>,
+
+	/// The ids of the tasks blocking this one (from `task_dependency`).
+	#[field(skip)]
+	#[sqlx(default)]
+	pub blocker_ids: Vec<i64>,
 
 	// -- Timestamps
 	pub cid: i64,
@@ -25,128 +70,2042 @@ pub struct Task {
 	pub mtime: OffsetDateTime,
 }
 
-#[derive(Deserialize, Fields)]
+impl HasId for Task {
+	fn id(&self) -> i64 {
+		self.id
+	}
+}
+
+/// A task with its (accessible and not deleted) subtasks, recursively.
+#[derive(Debug, Serialize)]
+pub struct TaskTree {
+	#[serde(flatten)]
+	pub task: Task,
+	pub children: Vec<TaskTree>,
+}
+
+#[serde_as]
+#[derive(Default, Deserialize, Fields)]
 pub struct TaskForCreate {
+	pub project_id: Option<i64>,
+	pub parent_id: Option<i64>,
 	pub title: String,
+	pub description: Option<String>,
+	pub status: Option<TaskStatus>,
+	pub priority: Option<i32>,
+	#[serde_as(as = "Option<Rfc3339>")]
+	#[serde(default)]
+	pub due_at: Option<OffsetDateTime>,
+	pub recurrence: Option<String>,
+}
+
+/// Note: The nullable properties are double options, where `Some(None)`
+///       (i.e., json `null`) clears the value, and `None` (absent) keeps it.
+#[derive(Default, Deserialize, Fields)]
+pub struct TaskForUpdate {
+	#[serde(default, with = "::serde_with::rust::double_option")]
+	pub project_id: Option<Option<i64>>,
+	#[serde(default, with = "::serde_with::rust::double_option")]
+	pub assignee_id: Option<Option<i64>>,
+	pub title: Option<String>,
+	#[serde(default, with = "::serde_with::rust::double_option")]
+	pub description: Option<Option<String>>,
+	pub status: Option<TaskStatus>,
+	pub priority: Option<i32>,
+	#[serde(default, deserialize_with = "deserialize_rfc3339_double_option")]
+	pub due_at: Option<Option<OffsetDateTime>>,
+	#[serde(default, with = "::serde_with::rust::double_option")]
+	pub recurrence: Option<Option<String>>,
+
+	/// The expected task version (i.e., `Task.version` as last read by the client).
+	/// When present, the update fails with `Error::VersionConflict` if the task
+	/// was updated since.
+	#[field(skip)]
+	pub version: Option<i64>,
+}
+
+/// Where to move a task to, relative to another task (see `TaskBmc::move_to_position`).
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum TaskPositionTarget {
+	#[serde(rename = "before_id")]
+	Before(i64),
+	#[serde(rename = "after_id")]
+	After(i64),
+}
+
+/// The `TaskBmc::create` insert data, i.e., the `TaskForCreate` and its computed position
+/// (and the assignee, for the next occurrences).
+/// (`HasFields` by hand, as for `TaskForMove`)
+struct TaskForInsert {
+	task_c: TaskForCreate,
+	assignee_id: Option<i64>,
+	position: String,
+}
+
+impl HasFields for TaskForInsert {
+	fn not_none_fields<'a>(self) -> Vec<Field<'a>> {
+		let mut fields = self.task_c.not_none_fields();
+		if let Some(assignee_id) = self.assignee_id {
+			fields.push(("assignee_id", assignee_id).into());
+		}
+		fields.push(("position", self.position).into());
+		fields
+	}
+
+	fn all_fields<'a>(self) -> Vec<Field<'a>> {
+		let mut fields = self.task_c.all_fields();
+		fields.push(("assignee_id", self.assignee_id).into());
+		fields.push(("position", self.position).into());
+		fields
+	}
+
+	fn field_names() -> &'static [&'static str] {
+		&[
+			"project_id",
+			"parent_id",
+			"title",
+			"description",
+			"status",
+			"priority",
+			"due_at",
+			"recurrence",
+			"assignee_id",
+			"position",
+		]
+	}
+}
+
+/// The update data to set (or clear) the task recurrence.
+/// (`HasFields` by hand, as for `TaskForMove`)
+struct TaskForRecurrence {
+	recurrence: Option<String>,
+}
+
+impl HasFields for TaskForRecurrence {
+	fn not_none_fields<'a>(self) -> Vec<Field<'a>> {
+		self.all_fields()
+	}
+
+	fn all_fields<'a>(self) -> Vec<Field<'a>> {
+		vec![("recurrence", self.recurrence).into()]
+	}
+
+	fn field_names() -> &'static [&'static str] {
+		&["recurrence"]
+	}
+}
+
+#[derive(Fields)]
+struct TaskForPosition {
+	position: String,
+}
+
+/// The `TaskBmc::assign` update data.
+/// (`HasFields` by hand, as for `TaskForMove`)
+struct TaskForAssign {
+	assignee_id: Option<i64>,
+}
+
+impl HasFields for TaskForAssign {
+	fn not_none_fields<'a>(self) -> Vec<Field<'a>> {
+		self.all_fields()
+	}
+
+	fn all_fields<'a>(self) -> Vec<Field<'a>> {
+		vec![("assignee_id", self.assignee_id).into()]
+	}
+
+	fn field_names() -> &'static [&'static str] {
+		&["assignee_id"]
+	}
+}
+
+/// The `TaskBmc::move_to_parent` update data.
+///
+/// Note: Implements `HasFields` by hand, so that a `None` parent_id is set as `NULL`
+///       (not skipped as with the `Fields` derive `not_none_fields`).
+struct TaskForMove {
+	parent_id: Option<i64>,
+}
+
+impl HasFields for TaskForMove {
+	fn not_none_fields<'a>(self) -> Vec<Field<'a>> {
+		self.all_fields()
+	}
+
+	fn all_fields<'a>(self) -> Vec<Field<'a>> {
+		vec![("parent_id", self.parent_id).into()]
+	}
+
+	fn field_names() -> &'static [&'static str] {
+		&["parent_id"]
+	}
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TaskFilter {
+	pub id: Option<OpValsInt64>,
+	pub project_id: Option<OpValsInt64>,
+	pub parent_id: Option<OpValsInt64>,
+	pub assignee_id: Option<OpValsInt64>,
+	/// When `true`, only the tasks assigned to the ctx user (`false` is ignored).
+	/// Note: Resolved from the `Ctx` by the `TaskBmc` list functions.
+	pub assigned_to_me: Option<bool>,
+	pub title: Option<OpValsString>,
+	pub status: Option<OpValsEnum<TaskStatus>>,
+	pub priority: Option<OpValsInt64>,
+	pub due_at: Option<OpValsTime>,
+	pub done_at: Option<OpValsTime>,
+	pub label_ids: Option<OpValsInt64Array>,
+
+	pub cid: Option<OpValsInt64>,
+	pub ctime: Option<OpValsTime>,
+	pub mid: Option<OpValsInt64>,
+	pub mtime: Option<OpValsTime>,
+}
+
+impl FilterNodes for TaskFilter {
+	fn filter_nodes(self) -> Vec<FilterNode> {
+		[
+			self.id.into_filter_nodes("id"),
+			self.project_id.into_filter_nodes("project_id"),
+			self.parent_id.into_filter_nodes("parent_id"),
+			self.assignee_id.into_filter_nodes("assignee_id"),
+			self.title.into_filter_nodes("title"),
+			self.status.into_filter_nodes("status"),
+			self.priority.into_filter_nodes("priority"),
+			self.due_at.into_filter_nodes("due_at"),
+			self.done_at.into_filter_nodes("done_at"),
+			self.label_ids.into_filter_nodes(LABEL_IDS_EXPR),
+			self.cid.into_filter_nodes("cid"),
+			self.ctime.into_filter_nodes("ctime"),
+			self.mid.into_filter_nodes("mid"),
+			self.mtime.into_filter_nodes("mtime"),
+		]
+		.into_iter()
+		.flatten()
+		.collect()
+	}
+}
+
+/// The `TaskFilter` with its `Ctx` dependent properties resolved (i.e., `assigned_to_me`).
+struct TaskFilterForCtx {
+	filter: TaskFilter,
+	user_id: i64,
+}
+
+impl TaskFilterForCtx {
+	fn new(ctx: &Ctx, filter: Option<TaskFilter>) -> Option<Self> {
+		filter.map(|filter| Self { filter, user_id: ctx.user_id() })
+	}
+}
+
+impl FilterNodes for TaskFilterForCtx {
+	fn filter_nodes(self) -> Vec<FilterNode> {
+		let Self { filter, user_id } = self;
+
+		let mut nodes = Vec::new();
+		if filter.assigned_to_me == Some(true) {
+			nodes.push(FilterNode::new(
+				"assignee_id",
+				"=",
+				FilterVal::Int64(user_id),
+			));
+		}
+		nodes.extend(filter.filter_nodes());
+
+		nodes
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+	Todo,
+	InProgress,
+	Done,
+	Cancelled,
+}
+
+impl TaskStatus {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			TaskStatus::Todo => "todo",
+			TaskStatus::InProgress => "in_progress",
+			TaskStatus::Done => "done",
+			TaskStatus::Cancelled => "cancelled",
+		}
+	}
+}
+
+impl AsRef<str> for TaskStatus {
+	fn as_ref(&self) -> &str {
+		self.as_str()
+	}
+}
+
+// region:    --- TaskStatus sqlx/sqlb Mapping
+// Note: Mapped to the `task_status` postgres enum type (encoded as text).
+
+impl Type<Postgres> for TaskStatus {
+	fn type_info() -> PgTypeInfo {
+		PgTypeInfo::with_name("task_status")
+	}
+}
+
+impl Encode<'_, Postgres> for TaskStatus {
+	fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> sqlx::encode::IsNull {
+		<&str as Encode<Postgres>>::encode(self.as_str(), buf)
+	}
+}
+
+impl<'r> Decode<'r, Postgres> for TaskStatus {
+	fn decode(
+		value: PgValueRef<'r>,
+	) -> core::result::Result<Self, sqlx::error::BoxDynError> {
+		match <&str as Decode<Postgres>>::decode(value)? {
+			"todo" => Ok(TaskStatus::Todo),
+			"in_progress" => Ok(TaskStatus::InProgress),
+			"done" => Ok(TaskStatus::Done),
+			"cancelled" => Ok(TaskStatus::Cancelled),
+			other => Err(format!("Unknown task_status '{other}'").into()),
+		}
+	}
+}
+
+impl SqlxBindable for TaskStatus {
+	fn bind_query<'q>(
+		&'q self,
+		query: sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>,
+	) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments> {
+		query.bind(*self)
+	}
+}
+
+// endregion: --- TaskStatus sqlx/sqlb Mapping
+// endregion: --- Task Types
+
+const LABEL_TABLE: &str = "task_label";
+const DEPENDENCY_TABLE: &str = "task_dependency";
+
+/// The task label ids array expression, for the `TaskFilter.label_ids` filter nodes.
+/// (left as is by `sqlb` because of the `(`)
+const LABEL_IDS_EXPR: &str = r#"(ARRAY(SELECT label_id FROM task_label WHERE task_label.task_id = "task"."id"))"#;
+
+pub struct TaskBmc;
+
+impl DbBmc for TaskBmc {
+	const TABLE: &'static str = "task";
+	const HAS_TIMESTAMPS: bool = true;
+	const SOFT_DELETE: bool = true;
+	const HAS_VERSION: bool = true;
+	const WORKSPACE_SCOPED: bool = true;
+	// Note: `search_tsv` is generated from the `SEARCH_COLUMNS`.
+	const AUDIT_EXCLUDED_COLUMNS: &'static [&'static str] = &["search_tsv"];
+	const SEARCH_COLUMNS: &'static [&'static str] = &["title", "description"];
+
+	/// Only the task creator and assignee can access it.
+	///
+	/// Note: `cid = $n OR assignee_id = $n` as the array overlap form,
+	///       since the filter nodes are `AND`ed (see `filter::in_node`).
+	fn access_filter_nodes(ctx: &Ctx) -> Vec<FilterNode> {
+		vec![FilterNode::new(
+			r#"(ARRAY["cid", "assignee_id"])"#,
+			"&&",
+			FilterVal::Int64s(vec![ctx.user_id()]),
+		)]
+	}
+}
+
+impl TaskBmc {
+	pub async fn create(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		mut task_c: TaskForCreate,
+	) -> Result<i64> {
+		task_c.recurrence = task_c
+			.recurrence
+			.as_deref()
+			.map(normalize_recurrence)
+			.transpose()?;
+
+		// Note: The project and parent must be accessible (and in the same workspace).
+		if let Some(project_id) = task_c.project_id {
+			ProjectBmc::get(ctx, mm, project_id).await?;
+		}
+		if let Some(parent_id) = task_c.parent_id {
+			base::get::<Self, Task>(ctx, mm, parent_id).await?;
+		}
+
+		let workspace_id = ctx
+			.workspace_id()
+			.ok_or(Error::WorkspaceNotInCtx { entity: Self::TABLE })?;
+
+		let mm = mm.begin().await?;
+
+		let position = Self::next_last_position(&mm, workspace_id).await?;
+		let task_fi = TaskForInsert { task_c, assignee_id: None, position };
+		let id = base::create::<Self, _>(ctx, &mm, task_fi).await?;
+
+		mm.commit().await?;
+
+		Ok(id)
+	}
+
+	pub async fn get(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Task> {
+		let mut task = base::get::<Self, _>(ctx, mm, id).await?;
+		Self::load_relations(mm, std::slice::from_mut(&mut task)).await?;
+
+		Ok(task)
+	}
+
+	pub async fn list(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		filter: Option<TaskFilter>,
+		list_options: Option<ListOptions>,
+	) -> Result<Vec<Task>> {
+		let mut tasks = base::list::<Self, _, _>(
+			ctx,
+			mm,
+			TaskFilterForCtx::new(ctx, filter),
+			list_options,
+		)
+		.await?;
+		Self::load_relations(mm, &mut tasks).await?;
+
+		Ok(tasks)
+	}
+
+	pub async fn list_page(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		filter: Option<TaskFilter>,
+		list_options: Option<ListOptions>,
+	) -> Result<Page<Task>> {
+		let mut page = base::list_page::<Self, _, _>(
+			ctx,
+			mm,
+			TaskFilterForCtx::new(ctx, filter),
+			list_options,
+		)
+		.await?;
+		Self::load_relations(mm, &mut page.data).await?;
+
+		Ok(page)
+	}
+
+	/// Full-text search of the tasks title and description (see `base::search_page`).
+	pub async fn search(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		search: &str,
+		filter: Option<TaskFilter>,
+		list_options: Option<ListOptions>,
+	) -> Result<Page<SearchHit<Task>>> {
+		let mut page = base::search_page::<Self, Task, _>(
+			ctx,
+			mm,
+			search,
+			TaskFilterForCtx::new(ctx, filter),
+			list_options,
+		)
+		.await?;
+		let mut tasks: Vec<&mut Task> =
+			page.data.iter_mut().map(|hit| &mut hit.entity).collect();
+		Self::load_relations(mm, &mut tasks).await?;
+
+		Ok(page)
+	}
+
+	pub async fn count(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		filter: Option<TaskFilter>,
+	) -> Result<i64> {
+		base::count::<Self, _>(ctx, mm, TaskFilterForCtx::new(ctx, filter)).await
+	}
+
+	/// Update the task, and when it is marked done with a recurrence,
+	/// create its next occurrence (in the same transaction).
+	pub async fn update(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		id: i64,
+		mut task_u: TaskForUpdate,
+	) -> Result<()> {
+		// Note: The project must be accessible (and in the same workspace).
+		if let Some(Some(project_id)) = task_u.project_id {
+			ProjectBmc::get(ctx, mm, project_id).await?;
+		}
+		if let Some(Some(assignee_id)) = task_u.assignee_id {
+			let task: Task = base::get::<Self, _>(ctx, mm, id).await?;
+			Self::check_assignee(ctx, mm, &task, assignee_id).await?;
+		}
+		if let Some(Some(recurrence)) = task_u.recurrence.as_mut() {
+			*recurrence = normalize_recurrence(recurrence)?;
+		}
+		let done = task_u.status == Some(TaskStatus::Done);
+
+		let mm = mm.begin().await?;
+
+		match task_u.version {
+			Some(version) => {
+				base::update_versioned::<Self, _>(ctx, &mm, id, version, task_u)
+					.await?
+			}
+			None => base::update::<Self, _>(ctx, &mm, id, task_u).await?,
+		}
+
+		if done {
+			Self::create_next_occurrence(ctx, &mm, id).await?;
+		}
+
+		mm.commit().await?;
+
+		Ok(())
+	}
+
+	pub async fn delete(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+		base::delete::<Self>(ctx, mm, id).await
+	}
+
+	pub async fn restore(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+		base::restore::<Self>(ctx, mm, id).await
+	}
+
+	/// Purge the task (and its subtasks, by cascade), with the blobs of their attachments.
+	pub async fn purge(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+		let mm = mm.begin().await?;
+
+		let blob_keys = AttachmentBmc::list_blob_keys_by_task_tree(&mm, id).await?;
+		base::purge::<Self>(ctx, &mm, id).await?;
+		mm.delete_blobs(blob_keys).await?;
+
+		mm.commit().await?;
+
+		Ok(())
+	}
+}
+
+// Labels.
+impl TaskBmc {
+	/// Attach the label to the task (no-op if already attached).
+	pub async fn attach_label(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		id: i64,
+		label_id: i64,
+	) -> Result<()> {
+		let dbx = mm.dbx();
+
+		// Note: Both must be accessible (and so in the same workspace).
+		base::get::<Self, Task>(ctx, mm, id).await?;
+		LabelBmc::get(ctx, mm, label_id).await?;
+
+		// Note: No pre-check, so that concurrent attaches do not conflict.
+		let query = sqlx::query(
+			r#"INSERT INTO task_label (task_id, label_id) VALUES ($1, $2)
+			   ON CONFLICT DO NOTHING"#,
+		)
+		.bind(id)
+		.bind(label_id);
+		dbx.exec_raw(query).await?;
+
+		Ok(())
+	}
+
+	/// Detach the label from the task (no-op if not attached).
+	pub async fn detach_label(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		id: i64,
+		label_id: i64,
+	) -> Result<()> {
+		let dbx = mm.dbx();
+
+		base::get::<Self, Task>(ctx, mm, id).await?;
+
+		let sb = sqlb::delete()
+			.table(LABEL_TABLE)
+			.and_where("task_id", "=", id)
+			.and_where("label_id", "=", label_id);
+		dbx.exec(&sb).await?;
+
+		Ok(())
+	}
+}
+
+// Assignment.
+impl TaskBmc {
+	/// Assign the task to the user (or unassign it if `None`).
+	///
+	/// The assignee must exist and be a member of the task workspace.
+	pub async fn assign(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		id: i64,
+		assignee_id: Option<i64>,
+	) -> Result<()> {
+		let task: Task = base::get::<Self, _>(ctx, mm, id).await?;
+
+		if let Some(assignee_id) = assignee_id {
+			Self::check_assignee(ctx, mm, &task, assignee_id).await?;
+		}
+
+		base::update::<Self, _>(ctx, mm, id, TaskForAssign { assignee_id }).await
+	}
+
+	/// The assignee must be a user member of the task workspace.
+	async fn check_assignee(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		task: &Task,
+		assignee_id: i64,
+	) -> Result<()> {
+		let _user: User = UserBmc::get(ctx, mm, assignee_id).await?;
+		if !WorkspaceBmc::is_member(ctx, mm, task.workspace_id, assignee_id).await? {
+			return Err(Error::TaskAssigneeNotMember {
+				id: task.id,
+				assignee_id,
+				workspace_id: task.workspace_id,
+			});
+		}
+
+		Ok(())
+	}
+}
+
+// Recurrence.
+impl TaskBmc {
+	/// Create the next occurrence of the (done) task, if it has a recurrence
+	/// which is not over, returning its id.
+	///
+	/// The recurrence rule is moved to the next occurrence, so that it is created once
+	/// (even if the task is marked done again).
+	async fn create_next_occurrence(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		id: i64,
+	) -> Result<Option<i64>> {
+		let task = Self::get(ctx, mm, id).await?;
+		let Some(recurrence) = task.recurrence.as_deref() else {
+			return Ok(None);
+		};
+		let recurrence: Recurrence = recurrence.parse()?;
+
+		let task_u = TaskForRecurrence { recurrence: None };
+		base::update::<Self, _>(ctx, mm, id, task_u).await?;
+
+		let due_at = task.due_at.unwrap_or_else(now_utc);
+		let Some((next_due_at, next_recurrence)) = recurrence.next(due_at)? else {
+			return Ok(None);
+		};
+
+		// -- Create the next occurrence (in the task workspace, for the root ctx).
+		// Note: Inserted as a copy, keeping the task creator, project, parent, assignee,
+		//       and labels, without their access checks (already passed by the task),
+		//       so that whoever may mark the task done also creates its next occurrence.
+		let ctx = ctx.clone().with_workspace_id(task.workspace_id);
+		let task_c = TaskForCreate {
+			project_id: task.project_id,
+			parent_id: task.parent_id,
+			title: task.title,
+			description: task.description,
+			status: None,
+			priority: Some(task.priority),
+			due_at: Some(next_due_at),
+			recurrence: Some(next_recurrence.to_string()),
+		};
+		let position = Self::next_last_position(mm, task.workspace_id).await?;
+		let task_fi = TaskForInsert {
+			task_c,
+			assignee_id: task.assignee_id,
+			position,
+		};
+		let next_id =
+			base::create_for::<Self, _>(&ctx, mm, task.cid, task_fi).await?;
+
+		let query = sqlx::query(
+			r#"INSERT INTO task_label (task_id, label_id)
+			   SELECT $1, label_id FROM task_label WHERE task_id = $2"#,
+		)
+		.bind(next_id)
+		.bind(id);
+		mm.dbx().exec_raw(query).await?;
+
+		Ok(Some(next_id))
+	}
 }
 
-#[derive(Deserialize, Fields)]
-pub struct TaskForUpdate {
-	pub title: Option<String>,
-}
-// endregion: --- Task Types
+// Position.
+impl TaskBmc {
+	/// Move the task right before or after the target task (in the position order),
+	/// updating only the task position.
+	pub async fn move_to_position(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		id: i64,
+		target: TaskPositionTarget,
+	) -> Result<()> {
+		let target_id = match target {
+			TaskPositionTarget::Before(target_id)
+			| TaskPositionTarget::After(target_id) => target_id,
+		};
+		if target_id == id {
+			return Ok(());
+		}
+
+		let mm = mm.begin().await?;
+
+		// Note: Both must be accessible (and so in the same workspace).
+		base::get::<Self, Task>(ctx, &mm, id).await?;
+		let target_task: Task = base::get::<Self, _>(ctx, &mm, target_id).await?;
+		Self::lock_workspace_tasks(&mm, target_task.workspace_id).await?;
+		// Note: Re-read after the lock, since a concurrent move may have moved it.
+		let target_task: Task = base::get::<Self, _>(ctx, &mm, target_id).await?;
+
+		// -- Get the neighbor of the target, on the side to move to.
+		let (cmp, dir) = match target {
+			TaskPositionTarget::Before(_) => ("<", "DESC"),
+			TaskPositionTarget::After(_) => (">", "ASC"),
+		};
+		let sql = format!(
+			r#"SELECT position FROM task
+			   WHERE workspace_id = $1 AND position {cmp} $2 AND id <> $3
+			   ORDER BY position {dir} LIMIT 1"#
+		);
+		let query = sqlx::query_as(&sql)
+			.bind(target_task.workspace_id)
+			.bind(&target_task.position)
+			.bind(id);
+		let neighbor: Option<(String,)> = mm.dbx().fetch_optional_as(query).await?;
+		let neighbor = neighbor.map(|(position,)| position);
+
+		let target_position = Some(target_task.position.as_str());
+		let position = match target {
+			TaskPositionTarget::Before(_) => {
+				key_between(neighbor.as_deref(), target_position)?
+			}
+			TaskPositionTarget::After(_) => {
+				key_between(target_position, neighbor.as_deref())?
+			}
+		};
+
+		base::update::<Self, _>(ctx, &mm, id, TaskForPosition { position }).await?;
+
+		mm.commit().await?;
+
+		Ok(())
+	}
+
+	/// The position after the last task of the workspace (locking the workspace tasks).
+	async fn next_last_position(
+		mm: &ModelManager,
+		workspace_id: i64,
+	) -> Result<String> {
+		Self::lock_workspace_tasks(mm, workspace_id).await?;
+
+		let query = sqlx::query_as(
+			r#"SELECT max(position) FROM task WHERE workspace_id = $1"#,
+		)
+		.bind(workspace_id);
+		let (last,): (Option<String>,) = mm.dbx().fetch_one_as(query).await?;
+
+		Ok(key_between(last.as_deref(), None)?)
+	}
+
+	/// Lock the workspace tasks ordering and graph (positions, parents, and blockers)
+	/// until the end of the `mm` transaction, so that concurrent edits cannot compute
+	/// the same position, or create a cycle their checks would miss.
+	///
+	/// Note: `FOR NO KEY UPDATE`, so that it does not block the other task inserts
+	///       (their `workspace_id` FK check only takes a `FOR KEY SHARE` lock).
+	async fn lock_workspace_tasks(
+		mm: &ModelManager,
+		workspace_id: i64,
+	) -> Result<()> {
+		let query =
+			sqlx::query("SELECT id FROM workspace WHERE id = $1 FOR NO KEY UPDATE")
+				.bind(workspace_id);
+		mm.dbx().exec_raw(query).await?;
+
+		Ok(())
+	}
+}
+
+// Hierarchy & Dependencies.
+impl TaskBmc {
+	/// Move the task under the parent (or to the top level if `None`).
+	///
+	/// Fails with `Error::TaskParentCycle` if the parent is the task or one of its subtasks.
+	pub async fn move_to_parent(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		id: i64,
+		parent_id: Option<i64>,
+	) -> Result<()> {
+		let mm = mm.begin().await?;
+
+		let task: Task = base::get::<Self, _>(ctx, &mm, id).await?;
+		if let Some(parent_id) = parent_id {
+			base::get::<Self, Task>(ctx, &mm, parent_id).await?;
+			Self::lock_workspace_tasks(&mm, task.workspace_id).await?;
+
+			// -- Check that the task is not an ancestor of (or is) the parent.
+			let query = sqlx::query_as(
+				r#"WITH RECURSIVE ancestor AS (
+				     SELECT id, parent_id FROM task WHERE id = $1
+				     UNION
+				     SELECT task.id, task.parent_id FROM task
+				     JOIN ancestor ON task.id = ancestor.parent_id
+				   )
+				   SELECT count(*) FROM ancestor WHERE id = $2"#,
+			)
+			.bind(parent_id)
+			.bind(id);
+			let (count,): (i64,) = mm.dbx().fetch_one_as(query).await?;
+			if count > 0 {
+				return Err(Error::TaskParentCycle { id, parent_id });
+			}
+		}
+
+		base::update::<Self, _>(ctx, &mm, id, TaskForMove { parent_id }).await?;
+
+		mm.commit().await?;
+
+		Ok(())
+	}
+
+	/// Add a blocker to the task (no-op if already added).
+	///
+	/// Fails with `Error::TaskBlockerCycle` if the blocker is the task
+	/// or is (transitively) blocked by it.
+	pub async fn add_blocker(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		id: i64,
+		blocker_id: i64,
+	) -> Result<()> {
+		let mm = mm.begin().await?;
+		let dbx = mm.dbx();
+
+		// Note: Both must be accessible (and so in the same workspace).
+		let task: Task = base::get::<Self, _>(ctx, &mm, id).await?;
+		base::get::<Self, Task>(ctx, &mm, blocker_id).await?;
+		Self::lock_workspace_tasks(&mm, task.workspace_id).await?;
+
+		// -- Check that the blocker is not the task or (transitively) blocked by it.
+		let query = sqlx::query_as(
+			r#"WITH RECURSIVE blocker AS (
+			     SELECT $1::bigint AS id
+			     UNION
+			     SELECT task_dependency.blocker_id FROM task_dependency
+			     JOIN blocker ON task_dependency.task_id = blocker.id
+			   )
+			   SELECT count(*) FROM blocker WHERE id = $2"#,
+		)
+		.bind(blocker_id)
+		.bind(id);
+		let (count,): (i64,) = dbx.fetch_one_as(query).await?;
+		if count > 0 {
+			return Err(Error::TaskBlockerCycle { id, blocker_id });
+		}
+
+		let query = sqlx::query(
+			r#"INSERT INTO task_dependency (task_id, blocker_id) VALUES ($1, $2)
+			   ON CONFLICT DO NOTHING"#,
+		)
+		.bind(id)
+		.bind(blocker_id);
+		dbx.exec_raw(query).await?;
+
+		mm.commit().await?;
+
+		Ok(())
+	}
+
+	/// Remove a blocker from the task (no-op if not a blocker).
+	pub async fn remove_blocker(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		id: i64,
+		blocker_id: i64,
+	) -> Result<()> {
+		let dbx = mm.dbx();
+
+		base::get::<Self, Task>(ctx, mm, id).await?;
+
+		let sb = sqlb::delete()
+			.table(DEPENDENCY_TABLE)
+			.and_where("task_id", "=", id)
+			.and_where("blocker_id", "=", blocker_id);
+		dbx.exec(&sb).await?;
+
+		Ok(())
+	}
+
+	/// Get the task with its subtasks, recursively.
+	///
+	/// Note: The subtasks are listed with the default list limit.
+	pub async fn get_tree(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		id: i64,
+	) -> Result<TaskTree> {
+		let task = Self::get(ctx, mm, id).await?;
+
+		let query = sqlx::query_as(
+			r#"WITH RECURSIVE descendant AS (
+			     SELECT id FROM task WHERE parent_id = $1
+			     UNION
+			     SELECT task.id FROM task
+			     JOIN descendant ON task.parent_id = descendant.id
+			   )
+			   SELECT id FROM descendant"#,
+		)
+		.bind(id);
+		let ids: Vec<(i64,)> = mm.dbx().fetch_all_as(query).await?;
+		let ids: Vec<i64> = ids.into_iter().map(|(id,)| id).collect();
+
+		// -- List them for the ctx (accessible and not deleted only).
+		let mut descendants = if ids.is_empty() {
+			Vec::new()
+		} else {
+			let filter = TaskFilter {
+				id: Some(OpValsInt64 { in_: Some(ids), ..Default::default() }),
+				..Default::default()
+			};
+			Self::list(ctx, mm, Some(filter), None).await?
+		};
+
+		Ok(build_tree(task, &mut descendants))
+	}
+}
+
+// Relations.
+impl TaskBmc {
+	/// Set the `Task.label_ids` and `Task.blocker_ids` of the tasks (one query each).
+	async fn load_relations<T>(mm: &ModelManager, tasks: &mut [T]) -> Result<()>
+	where
+		T: BorrowMut<Task>,
+	{
+		if tasks.is_empty() {
+			return Ok(());
+		}
+
+		let task_ids: Vec<i64> = tasks.iter().map(|t| t.borrow().id).collect();
+
+		let query = sqlx::query_as(
+			r#"SELECT task_id, label_id FROM task_label
+			   WHERE task_id = ANY($1)
+			   ORDER BY label_id"#,
+		)
+		.bind(&task_ids);
+		let label_rows: Vec<(i64, i64)> = mm.dbx().fetch_all_as(query).await?;
+
+		let query = sqlx::query_as(
+			r#"SELECT task_id, blocker_id FROM task_dependency
+			   WHERE task_id = ANY($1)
+			   ORDER BY blocker_id"#,
+		)
+		.bind(&task_ids);
+		let blocker_rows: Vec<(i64, i64)> = mm.dbx().fetch_all_as(query).await?;
+
+		let ids_of = |rows: &[(i64, i64)], id: i64| -> Vec<i64> {
+			rows.iter()
+				.filter(|(task_id, _)| *task_id == id)
+				.map(|(_, other_id)| *other_id)
+				.collect()
+		};
+		for task in tasks.iter_mut() {
+			let task = task.borrow_mut();
+			task.label_ids = ids_of(&label_rows, task.id);
+			task.blocker_ids = ids_of(&blocker_rows, task.id);
+		}
+
+		Ok(())
+	}
+}
+
+/// Validate the recurrence rule, returning its normalized form.
+fn normalize_recurrence(recurrence: &str) -> Result<String> {
+	Ok(recurrence.parse::<Recurrence>()?.to_string())
+}
+
+/// The `serde_with::rust::double_option` deserialize of a Rfc3339 time.
+fn deserialize_rfc3339_double_option<'de, D>(
+	deserializer: D,
+) -> core::result::Result<Option<Option<OffsetDateTime>>, D::Error>
+where
+	D: Deserializer<'de>,
+{
+	As::<Option<Rfc3339>>::deserialize(deserializer).map(Some)
+}
+
+/// Build the tree of `task`, taking its children (recursively) out of `tasks`.
+fn build_tree(task: Task, tasks: &mut Vec<Task>) -> TaskTree {
+	let (children, rest): (Vec<Task>, Vec<Task>) = std::mem::take(tasks)
+		.into_iter()
+		.partition(|t| t.parent_id == Some(task.id));
+	*tasks = rest;
+
+	let children = children
+		.into_iter()
+		.map(|child| build_tree(child, tasks))
+		.collect();
+
+	TaskTree { task, children }
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::_dev_utils::{self, DEMO_WORKSPACE_ID};
+	use crate::model::project::ProjectForCreate;
+	use crate::model::workspace::{WorkspaceBmc, WorkspaceForCreate};
+	use crate::model::Error;
+	use anyhow::{Context, Result};
+	use lib_base::time::{format_time, parse_utc};
+	use serial_test::serial;
+
+	#[serial]
+	#[tokio::test]
+	async fn test_create_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_title = "test_model_task_create_basic";
+
+		// -- Exec
+		let task_c = TaskForCreate {
+			title: fx_title.to_string(),
+			..Default::default()
+		};
+		let id = TaskBmc::create(&ctx, &mm, task_c).await?;
+
+		// -- Check
+		let task = TaskBmc::get(&ctx, &mm, id).await?;
+		assert_eq!(task.title, fx_title);
+
+		// -- Clean
+		TaskBmc::purge(&ctx, &mm, id).await?;
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_create_in_txn_commit_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_title = "test_create_in_txn_commit_ok";
+
+		// -- Exec
+		let mm_txn = mm.begin().await?;
+		let task_c = TaskForCreate {
+			title: fx_title.to_string(),
+			..Default::default()
+		};
+		let id = TaskBmc::create(&ctx, &mm_txn, task_c).await?;
+
+		// -- Check - not visible outside the txn before commit
+		let res = TaskBmc::get(&ctx, &mm, id).await;
+		assert!(
+			matches!(res, Err(Error::EntityNotFound { .. })),
+			"Should not be visible outside txn. res: {res:?}"
+		);
+
+		// -- Check - visible after commit
+		mm_txn.commit().await?;
+		let task = TaskBmc::get(&ctx, &mm, id).await?;
+		assert_eq!(task.title, fx_title);
+
+		// -- Clean
+		TaskBmc::purge(&ctx, &mm, id).await?;
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_create_in_txn_rollback_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_title = "test_create_in_txn_rollback_ok";
+
+		// -- Exec
+		let mm_txn = mm.begin().await?;
+		let task_c = TaskForCreate {
+			title: fx_title.to_string(),
+			..Default::default()
+		};
+		let id = TaskBmc::create(&ctx, &mm_txn, task_c).await?;
+		// Nested begin joins the txn, and its commit is a no-op.
+		let mm_nested = mm_txn.begin().await?;
+		TaskBmc::update(
+			&ctx,
+			&mm_nested,
+			id,
+			TaskForUpdate {
+				title: Some(format!("{fx_title} - updated")),
+				..Default::default()
+			},
+		)
+		.await?;
+		mm_nested.commit().await?;
+		mm_txn.rollback().await?;
+
+		// -- Check
+		let res = TaskBmc::get(&ctx, &mm, id).await;
+		assert!(
+			matches!(res, Err(Error::EntityNotFound { .. })),
+			"Should have been rolled back. res: {res:?}"
+		);
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_get_err_not_found() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_id = 100;
+
+		// -- Exec
+		let res = TaskBmc::get(&ctx, &mm, fx_id).await;
+
+		// -- Check
+		assert!(
+			matches!(res, Err(Error::EntityNotFound { entity: "task", id: 100 })),
+			"EntityNotFound not matching"
+		);
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_list_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_titles = &["test_list_ok 01", "test_list_ok 02"];
+		_dev_utils::seed_tasks(&ctx, &mm, fx_titles).await?;
+
+		// -- Exec
+		let tasks = TaskBmc::list(&ctx, &mm, None, None).await?;
+
+		// -- Check
+		let tasks: Vec<Task> = tasks
+			.into_iter()
+			.filter(|t| t.title.starts_with("test_list_ok"))
+			.collect();
+		assert_eq!(tasks.len(), 2, "number of seeded tasks.");
+
+		// -- Clean
+		for task in tasks.iter() {
+			TaskBmc::purge(&ctx, &mm, task.id).await?;
+		}
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_list_by_filter_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_titles = &[
+			"test_list_by_filter_ok 01.a",
+			"test_list_by_filter_ok 01.b",
+			"test_list_by_filter_ok 02.a",
+			"test_list_by_filter_ok 02.b",
+			"test_list_by_filter_ok 03",
+		];
+		let fx_tasks = _dev_utils::seed_tasks(&ctx, &mm, fx_titles).await?;
+
+		// -- Exec
+		let filter = TaskFilter {
+			id: Some(OpValsInt64 {
+				not: Some(fx_tasks[1].id),
+				..Default::default()
+			}),
+			title: Some(OpValsString {
+				starts_with: Some("test_list_by_filter_ok 0".to_string()),
+				ends_with: Some(".a".to_string()),
+				..Default::default()
+			}),
+			..Default::default()
+		};
+		let tasks = TaskBmc::list(&ctx, &mm, Some(filter), None).await?;
+
+		// -- Check
+		let titles: Vec<&str> = tasks.iter().map(|t| t.title.as_str()).collect();
+		assert_eq!(
+			titles,
+			&["test_list_by_filter_ok 01.a", "test_list_by_filter_ok 02.a"]
+		);
+
+		// -- Clean
+		for task in fx_tasks.iter() {
+			TaskBmc::purge(&ctx, &mm, task.id).await?;
+		}
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_list_with_list_options_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_titles = &[
+			"test_list_with_list_options_ok 01",
+			"test_list_with_list_options_ok 02",
+			"test_list_with_list_options_ok 03",
+		];
+		let fx_tasks = _dev_utils::seed_tasks(&ctx, &mm, fx_titles).await?;
+		let fx_ids: Vec<i64> = fx_tasks.iter().map(|t| t.id).collect();
+
+		// -- Exec
+		let filter = TaskFilter {
+			id: Some(OpValsInt64 { in_: Some(fx_ids), ..Default::default() }),
+			..Default::default()
+		};
+		let list_options = ListOptions {
+			limit: Some(2),
+			offset: Some(1),
+			order_bys: Some(vec!["!title".to_string()]),
+			cursor: None,
+		};
+		let tasks =
+			TaskBmc::list(&ctx, &mm, Some(filter), Some(list_options)).await?;
+
+		// -- Check
+		let titles: Vec<&str> = tasks.iter().map(|t| t.title.as_str()).collect();
+		assert_eq!(
+			titles,
+			&[
+				"test_list_with_list_options_ok 02",
+				"test_list_with_list_options_ok 01"
+			]
+		);
+
+		// -- Clean
+		for task in fx_tasks.iter() {
+			TaskBmc::purge(&ctx, &mm, task.id).await?;
+		}
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_list_page_with_cursor_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_titles = &[
+			"test_list_page_with_cursor_ok 01",
+			"test_list_page_with_cursor_ok 02",
+			"test_list_page_with_cursor_ok 03",
+		];
+		let fx_tasks = _dev_utils::seed_tasks(&ctx, &mm, fx_titles).await?;
+		let fx_filter = || TaskFilter {
+			title: Some(OpValsString {
+				starts_with: Some("test_list_page_with_cursor_ok".to_string()),
+				..Default::default()
+			}),
+			..Default::default()
+		};
+
+		// -- Exec
+		let list_options = ListOptions { limit: Some(2), ..Default::default() };
+		let page_1 =
+			TaskBmc::list_page(&ctx, &mm, Some(fx_filter()), Some(list_options))
+				.await?;
+		let list_options = ListOptions {
+			limit: Some(2),
+			cursor: page_1.next_cursor.clone(),
+			..Default::default()
+		};
+		let page_2 =
+			TaskBmc::list_page(&ctx, &mm, Some(fx_filter()), Some(list_options))
+				.await?;
+
+		// -- Check
+		let ids_1: Vec<i64> = page_1.data.iter().map(|t| t.id).collect();
+		let ids_2: Vec<i64> = page_2.data.iter().map(|t| t.id).collect();
+		assert_eq!(ids_1, &[fx_tasks[0].id, fx_tasks[1].id]);
+		assert_eq!(ids_2, &[fx_tasks[2].id]);
+		assert!(
+			page_1.next_cursor.is_some(),
+			"page_1 should have next_cursor"
+		);
+		assert!(
+			page_2.next_cursor.is_none(),
+			"page_2 should be the last page"
+		);
+
+		// -- Clean
+		for task in fx_tasks.iter() {
+			TaskBmc::purge(&ctx, &mm, task.id).await?;
+		}
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_count_by_filter_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_titles = &[
+			"test_count_by_filter_ok 01",
+			"test_count_by_filter_ok 02",
+			"test_count_by_filter_ok 03",
+		];
+		let fx_tasks = _dev_utils::seed_tasks(&ctx, &mm, fx_titles).await?;
+
+		// -- Exec
+		let filter = TaskFilter {
+			title: Some(OpValsString {
+				starts_with: Some("test_count_by_filter_ok".to_string()),
+				not: Some("test_count_by_filter_ok 02".to_string()),
+				..Default::default()
+			}),
+			..Default::default()
+		};
+		let count = TaskBmc::count(&ctx, &mm, Some(filter)).await?;
+
+		// -- Check
+		assert_eq!(count, 2);
+
+		// -- Clean
+		for task in fx_tasks.iter() {
+			TaskBmc::purge(&ctx, &mm, task.id).await?;
+		}
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_list_err_order_by_unknown() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_order_by = "count(*)";
+
+		// -- Exec
+		let list_options = ListOptions {
+			order_bys: Some(vec![fx_order_by.to_string()]),
+			..Default::default()
+		};
+		let res = TaskBmc::list(&ctx, &mm, None, Some(list_options)).await;
+
+		// -- Check
+		assert!(
+			matches!(&res, Err(Error::ListOrderByUnknown(s)) if s == fx_order_by),
+			"ListOrderByUnknown not matching. res: {res:?}"
+		);
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_update_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_title = "test_update_ok - task 01";
+		let fx_title_new = "test_update_ok - task 01 - new";
+		let fx_task = _dev_utils::seed_tasks(&ctx, &mm, &[fx_title])
+			.await?
+			.remove(0);
+
+		// -- Exec
+		TaskBmc::update(
+			&ctx,
+			&mm,
+			fx_task.id,
+			TaskForUpdate {
+				title: Some(fx_title_new.to_string()),
+				..Default::default()
+			},
+		)
+		.await?;
+
+		// -- Check
+		let task = TaskBmc::get(&ctx, &mm, fx_task.id).await?;
+		assert_eq!(task.title, fx_title_new);
+		assert_eq!(task.version, fx_task.version + 1);
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_update_clear_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_title = "test_update_clear_ok - task 01";
+		let id = TaskBmc::create(
+			&ctx,
+			&mm,
+			TaskForCreate {
+				title: fx_title.to_string(),
+				description: Some("some description".to_string()),
+				due_at: Some(parse_utc("2030-01-02T03:04:05Z")?),
+				recurrence: Some("FREQ=DAILY".to_string()),
+				..Default::default()
+			},
+		)
+		.await?;
+
+		// -- Exec
+		// Absent properties are kept, and null ones cleared.
+		let task_u: TaskForUpdate = serde_json::from_value(serde_json::json!({
+			"description": null,
+			"due_at": null,
+			"recurrence": null,
+		}))?;
+		TaskBmc::update(&ctx, &mm, id, task_u).await?;
+
+		// -- Check
+		let task = TaskBmc::get(&ctx, &mm, id).await?;
+		assert_eq!(task.title, fx_title);
+		assert_eq!(task.description, None);
+		assert_eq!(task.due_at, None);
+		assert_eq!(task.recurrence, None);
+
+		// -- Clean
+		TaskBmc::purge(&ctx, &mm, id).await?;
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_update_with_version_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_title = "test_update_with_version_ok - task 01";
+		let fx_title_new = "test_update_with_version_ok - task 01 - new";
+		let fx_task = _dev_utils::seed_tasks(&ctx, &mm, &[fx_title])
+			.await?
+			.remove(0);
+
+		// -- Exec
+		TaskBmc::update(
+			&ctx,
+			&mm,
+			fx_task.id,
+			TaskForUpdate {
+				title: Some(fx_title_new.to_string()),
+				version: Some(fx_task.version),
+				..Default::default()
+			},
+		)
+		.await?;
+
+		// -- Check
+		let task = TaskBmc::get(&ctx, &mm, fx_task.id).await?;
+		assert_eq!(task.title, fx_title_new);
+		assert_eq!(task.version, fx_task.version + 1);
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_update_without_version_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_title = "test_update_without_version_ok - task 01";
+		let fx_task = _dev_utils::seed_tasks(&ctx, &mm, &[fx_title])
+			.await?
+			.remove(0);
+		let fx_task_u = |suffix: &str| TaskForUpdate {
+			title: Some(format!("{fx_title} - {suffix}")),
+			..Default::default()
+		};
+
+		// -- Exec
+		// No version given, so no conflict check (last write wins).
+		TaskBmc::update(&ctx, &mm, fx_task.id, fx_task_u("new 01")).await?;
+		TaskBmc::update(&ctx, &mm, fx_task.id, fx_task_u("new 02")).await?;
+
+		// -- Check
+		let task = TaskBmc::get(&ctx, &mm, fx_task.id).await?;
+		assert_eq!(task.title, format!("{fx_title} - new 02"));
+		assert_eq!(task.version, fx_task.version + 2);
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_update_err_version_conflict() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_title = "test_update_err_version_conflict - task 01";
+		let fx_task = _dev_utils::seed_tasks(&ctx, &mm, &[fx_title])
+			.await?
+			.remove(0);
+		let fx_task_u = || TaskForUpdate {
+			title: Some(format!("{fx_title} - new")),
+			version: Some(fx_task.version),
+			..Default::default()
+		};
+
+		// -- Exec
+		// First update (e.g., first browser tab) succeeds.
+		TaskBmc::update(&ctx, &mm, fx_task.id, fx_task_u()).await?;
+		// Second update with the same version (e.g., second tab) conflicts.
+		let res = TaskBmc::update(&ctx, &mm, fx_task.id, fx_task_u()).await;
+
+		// -- Check
+		let expected = fx_task.version;
+		let actual = fx_task.version + 1;
+		assert!(
+			matches!(
+				res,
+				Err(Error::VersionConflict { entity: "task", expected: e, actual: a, .. })
+					if e == expected && a == actual
+			),
+			"VersionConflict not matching. Actual: {res:?}"
+		);
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_delete_err_not_found() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_id = 100;
+
+		// -- Exec
+		let res = TaskBmc::delete(&ctx, &mm, fx_id).await;
+
+		// -- Check
+		assert!(
+			matches!(res, Err(Error::EntityNotFound { entity: "task", id: 100 })),
+			"EntityNotFound not matching"
+		);
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_delete_soft_and_restore_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_title = "test_delete_soft_and_restore_ok - task 01";
+		let fx_task = _dev_utils::seed_tasks(&ctx, &mm, &[fx_title])
+			.await?
+			.remove(0);
+		let fx_filter = || TaskFilter {
+			title: Some(OpValsString {
+				eq: Some(fx_title.to_string()),
+				..Default::default()
+			}),
+			..Default::default()
+		};
+
+		// -- Exec & Check - delete
+		TaskBmc::delete(&ctx, &mm, fx_task.id).await?;
+		let res = TaskBmc::get(&ctx, &mm, fx_task.id).await;
+		assert!(
+			matches!(res, Err(Error::EntityNotFound { entity: "task", .. })),
+			"Soft deleted task should not be found. Actual: {res:?}"
+		);
+		assert_eq!(TaskBmc::count(&ctx, &mm, Some(fx_filter())).await?, 0);
+		let res = TaskBmc::delete(&ctx, &mm, fx_task.id).await;
+		assert!(
+			matches!(res, Err(Error::EntityNotFound { .. })),
+			"Soft deleted task should not be deleted again. Actual: {res:?}"
+		);
+
+		// -- Exec & Check - restore
+		TaskBmc::restore(&ctx, &mm, fx_task.id).await?;
+		let task = TaskBmc::get(&ctx, &mm, fx_task.id).await?;
+		assert_eq!(task.title, fx_title);
+		let tasks = TaskBmc::list(&ctx, &mm, Some(fx_filter()), None).await?;
+		assert_eq!(tasks.len(), 1);
+		let res = TaskBmc::restore(&ctx, &mm, fx_task.id).await;
+		assert!(
+			matches!(res, Err(Error::EntityNotFound { .. })),
+			"Not deleted task should not be restored. Actual: {res:?}"
+		);
+
+		// -- Clean
+		TaskBmc::purge(&ctx, &mm, fx_task.id).await?;
+
+		Ok(())
+	}
 
-pub struct TaskBmc;
+	#[serial]
+	#[tokio::test]
+	async fn test_purge_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_title = "test_purge_ok - task 01";
+		let fx_task = _dev_utils::seed_tasks(&ctx, &mm, &[fx_title])
+			.await?
+			.remove(0);
+		TaskBmc::delete(&ctx, &mm, fx_task.id).await?;
 
-impl DbBmc for TaskBmc {
-	const TABLE: &'static str = "task";
-	const HAS_TIMESTAMPS: bool = true;
-}
+		// -- Exec
+		TaskBmc::purge(&ctx, &mm, fx_task.id).await?;
 
-impl TaskBmc {
-	pub async fn create(
-		ctx: &Ctx,
-		mm: &ModelManager,
-		task_c: TaskForCreate,
-	) -> Result<i64> {
-		base::create::<Self, _>(ctx, mm, task_c).await
+		// -- Check
+		let res = TaskBmc::restore(&ctx, &mm, fx_task.id).await;
+		assert!(
+			matches!(res, Err(Error::EntityNotFound { .. })),
+			"Purged task should not be restored. Actual: {res:?}"
+		);
+
+		Ok(())
 	}
 
-	pub async fn get(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Task> {
-		base::get::<Self, _>(ctx, mm, id).await
+	#[serial]
+	#[tokio::test]
+	async fn test_access_err_not_owner() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let root_ctx = Ctx::root_ctx();
+		let fx_ctx_owner = Ctx::new(1000)?.with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_ctx_other = Ctx::new(1001)?.with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_title = "test_access_err_not_owner - task 01";
+		let fx_task = _dev_utils::seed_tasks(&fx_ctx_owner, &mm, &[fx_title])
+			.await?
+			.remove(0);
+		let fx_filter = || TaskFilter {
+			title: Some(OpValsString {
+				eq: Some(fx_title.to_string()),
+				..Default::default()
+			}),
+			..Default::default()
+		};
+
+		// -- Exec & Check - other
+		let res = TaskBmc::get(&fx_ctx_other, &mm, fx_task.id).await;
+		assert!(
+			matches!(res, Err(Error::EntityNotFound { entity: "task", .. })),
+			"get by other should be EntityNotFound. Actual: {res:?}"
+		);
+		let res = TaskBmc::update(
+			&fx_ctx_other,
+			&mm,
+			fx_task.id,
+			TaskForUpdate {
+				title: Some(format!("{fx_title} - other")),
+				..Default::default()
+			},
+		)
+		.await;
+		assert!(
+			matches!(res, Err(Error::EntityNotFound { entity: "task", .. })),
+			"update by other should be EntityNotFound. Actual: {res:?}"
+		);
+		let res = TaskBmc::delete(&fx_ctx_other, &mm, fx_task.id).await;
+		assert!(
+			matches!(res, Err(Error::EntityNotFound { entity: "task", .. })),
+			"delete by other should be EntityNotFound. Actual: {res:?}"
+		);
+		let count = TaskBmc::count(&fx_ctx_other, &mm, Some(fx_filter())).await?;
+		assert_eq!(count, 0, "other should not list the task");
+
+		// -- Exec & Check - owner & root
+		let task = TaskBmc::get(&fx_ctx_owner, &mm, fx_task.id).await?;
+		assert_eq!(task.title, fx_title);
+		let count = TaskBmc::count(&root_ctx, &mm, Some(fx_filter())).await?;
+		assert_eq!(count, 1, "root ctx should bypass the access policy");
+
+		// -- Clean
+		TaskBmc::purge(&fx_ctx_owner, &mm, fx_task.id).await?;
+
+		Ok(())
 	}
 
-	pub async fn list(ctx: &Ctx, mm: &ModelManager) -> Result<Vec<Task>> {
-		base::list::<Self, _>(ctx, mm).await
+	#[serial]
+	#[tokio::test]
+	async fn test_workspace_scope_err_other_workspace() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let root_ctx = Ctx::root_ctx();
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_title = "test_workspace_scope_err_other_workspace - task 01";
+		let fx_task = _dev_utils::seed_tasks(&ctx, &mm, &[fx_title])
+			.await?
+			.remove(0);
+		let fx_workspace_id = WorkspaceBmc::create(
+			&root_ctx,
+			&mm,
+			WorkspaceForCreate {
+				name: "test_workspace_scope_err_other_workspace".to_string(),
+			},
+		)
+		.await?;
+		let fx_ctx_other = Ctx::root_ctx().with_workspace_id(fx_workspace_id);
+
+		// -- Exec
+		let res = TaskBmc::get(&fx_ctx_other, &mm, fx_task.id).await;
+
+		// -- Check
+		assert_eq!(fx_task.workspace_id, DEMO_WORKSPACE_ID);
+		assert!(
+			matches!(res, Err(Error::EntityNotFound { entity: "task", .. })),
+			"Task of another workspace should not be found. Actual: {res:?}"
+		);
+		let res = TaskBmc::create(
+			&Ctx::new(1000)?,
+			&mm,
+			TaskForCreate {
+				title: fx_title.to_string(),
+				..Default::default()
+			},
+		)
+		.await;
+		assert!(
+			matches!(res, Err(Error::WorkspaceNotInCtx { entity: "task" })),
+			"Create without workspace should fail. Actual: {res:?}"
+		);
+
+		// -- Clean
+		TaskBmc::purge(&ctx, &mm, fx_task.id).await?;
+		base::purge::<WorkspaceBmc>(&root_ctx, &mm, fx_workspace_id).await?;
+
+		Ok(())
 	}
 
-	pub async fn update(
-		ctx: &Ctx,
-		mm: &ModelManager,
-		id: i64,
-		task_u: TaskForUpdate,
-	) -> Result<()> {
-		base::update::<Self, _>(ctx, mm, id, task_u).await
+	#[serial]
+	#[tokio::test]
+	async fn test_create_rich_fields_and_done_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_title = "test_create_rich_fields_and_done_ok - task 01";
+		let fx_due_at = OffsetDateTime::parse("2030-01-02T03:04:05Z", &Rfc3339)?;
+
+		// -- Exec
+		let id = TaskBmc::create(
+			&ctx,
+			&mm,
+			TaskForCreate {
+				title: fx_title.to_string(),
+				description: Some("some description".to_string()),
+				priority: Some(3),
+				due_at: Some(fx_due_at),
+				..Default::default()
+			},
+		)
+		.await?;
+
+		// -- Check - create
+		let task = TaskBmc::get(&ctx, &mm, id).await?;
+		assert_eq!(task.description.as_deref(), Some("some description"));
+		assert_eq!(task.status, TaskStatus::Todo);
+		assert_eq!(task.priority, 3);
+		assert_eq!(task.due_at, Some(fx_due_at));
+		assert_eq!(task.done_at, None);
+
+		// -- Check - done
+		let task_u = TaskForUpdate {
+			status: Some(TaskStatus::Done),
+			..Default::default()
+		};
+		TaskBmc::update(&ctx, &mm, id, task_u).await?;
+		let task = TaskBmc::get(&ctx, &mm, id).await?;
+		assert_eq!(task.status, TaskStatus::Done);
+		assert!(task.done_at.is_some(), "done_at should be set when done");
+
+		// -- Check - back in progress
+		let task_u = TaskForUpdate {
+			status: Some(TaskStatus::InProgress),
+			..Default::default()
+		};
+		TaskBmc::update(&ctx, &mm, id, task_u).await?;
+		let task = TaskBmc::get(&ctx, &mm, id).await?;
+		assert_eq!(task.done_at, None);
+
+		// -- Clean
+		TaskBmc::purge(&ctx, &mm, id).await?;
+
+		Ok(())
 	}
 
-	pub async fn delete(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
-		base::delete::<Self>(ctx, mm, id).await
+	#[serial]
+	#[tokio::test]
+	async fn test_list_by_status_and_due_at_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_prefix = "test_list_by_status_and_due_at_ok";
+		let fx_due_at = OffsetDateTime::parse("2030-01-02T03:04:05Z", &Rfc3339)?;
+		let fx_tasks = [
+			(TaskStatus::Todo, None),
+			(TaskStatus::InProgress, Some(fx_due_at)),
+			(TaskStatus::Done, Some(fx_due_at)),
+		];
+		let mut ids = Vec::new();
+		for (i, (status, due_at)) in fx_tasks.into_iter().enumerate() {
+			let task_c = TaskForCreate {
+				title: format!("{fx_prefix} - task {i}"),
+				status: Some(status),
+				due_at,
+				..Default::default()
+			};
+			ids.push(TaskBmc::create(&ctx, &mm, task_c).await?);
+		}
+
+		// -- Exec
+		let filter: TaskFilter = serde_json::from_value(serde_json::json!({
+			"title": {"$starts_with": fx_prefix},
+			"status": {"$not": "done"},
+			"due_at": {"$lte": "2030-01-02T03:04:05Z"}
+		}))?;
+		let tasks = TaskBmc::list(&ctx, &mm, Some(filter), None).await?;
+
+		// -- Check
+		assert_eq!(tasks.len(), 1);
+		assert_eq!(tasks[0].status, TaskStatus::InProgress);
+		let res = serde_json::from_value::<TaskFilter>(
+			serde_json::json!({"status": {"$eq": "unknown"}}),
+		);
+		assert!(res.is_err(), "Unknown status should fail. Actual: {res:?}");
+
+		// -- Clean
+		for id in ids {
+			TaskBmc::purge(&ctx, &mm, id).await?;
+		}
+
+		Ok(())
 	}
-}
 
-// region:    --- Tests
-#[cfg(test)]
-mod tests {
-	use super::*;
-	use crate::_dev_utils;
-	use crate::model::Error;
-	use anyhow::Result;
-	use serial_test::serial;
+	#[serial]
+	#[tokio::test]
+	async fn test_move_and_get_tree_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_prefix = "test_move_and_get_tree_ok";
+		let mut ids = Vec::new();
+		for i in 0..3 {
+			let task_c = TaskForCreate {
+				title: format!("{fx_prefix} - task {i}"),
+				..Default::default()
+			};
+			ids.push(TaskBmc::create(&ctx, &mm, task_c).await?);
+		}
+
+		// -- Exec
+		// task 0 > task 1 > task 2
+		TaskBmc::move_to_parent(&ctx, &mm, ids[1], Some(ids[0])).await?;
+		TaskBmc::move_to_parent(&ctx, &mm, ids[2], Some(ids[1])).await?;
+		let res = TaskBmc::move_to_parent(&ctx, &mm, ids[0], Some(ids[2])).await;
+
+		// -- Check
+		assert!(
+			matches!(res, Err(Error::TaskParentCycle { id, .. }) if id == ids[0]),
+			"Should fail on parent cycle. Actual: {res:?}"
+		);
+		let tree = TaskBmc::get_tree(&ctx, &mm, ids[0]).await?;
+		assert_eq!(tree.task.id, ids[0]);
+		assert_eq!(tree.children.len(), 1);
+		assert_eq!(tree.children[0].task.id, ids[1]);
+		assert_eq!(tree.children[0].children[0].task.id, ids[2]);
+
+		// -- Exec & Check - move back to the top level
+		TaskBmc::move_to_parent(&ctx, &mm, ids[2], None).await?;
+		let task = TaskBmc::get(&ctx, &mm, ids[2]).await?;
+		assert_eq!(task.parent_id, None);
+
+		// -- Clean
+		TaskBmc::purge(&ctx, &mm, ids[0]).await?;
+		TaskBmc::purge(&ctx, &mm, ids[2]).await?;
+
+		Ok(())
+	}
 
 	#[serial]
 	#[tokio::test]
-	async fn test_create_ok() -> Result<()> {
+	async fn test_add_blocker_err_cycle() -> Result<()> {
 		// -- Setup & Fixtures
 		let mm = _dev_utils::init_test().await;
-		let ctx = Ctx::root_ctx();
-		let fx_title = "test_model_task_create_basic";
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_prefix = "test_add_blocker_err_cycle";
+		let mut ids = Vec::new();
+		for i in 0..3 {
+			let task_c = TaskForCreate {
+				title: format!("{fx_prefix} - task {i}"),
+				..Default::default()
+			};
+			ids.push(TaskBmc::create(&ctx, &mm, task_c).await?);
+		}
 
 		// -- Exec
-		let task_c = TaskForCreate { title: fx_title.to_string() };
-		let id = TaskBmc::create(&ctx, &mm, task_c).await?;
+		// task 0 blocked by task 1, blocked by task 2
+		TaskBmc::add_blocker(&ctx, &mm, ids[0], ids[1]).await?;
+		TaskBmc::add_blocker(&ctx, &mm, ids[1], ids[2]).await?;
+		let res = TaskBmc::add_blocker(&ctx, &mm, ids[2], ids[0]).await;
+		let res_self = TaskBmc::add_blocker(&ctx, &mm, ids[0], ids[0]).await;
 
 		// -- Check
-		let task = TaskBmc::get(&ctx, &mm, id).await?;
-		assert_eq!(task.title, fx_title);
+		assert!(
+			matches!(res, Err(Error::TaskBlockerCycle { id, .. }) if id == ids[2]),
+			"Should fail on blocker cycle. Actual: {res:?}"
+		);
+		assert!(
+			matches!(res_self, Err(Error::TaskBlockerCycle { .. })),
+			"Should fail on self blocker. Actual: {res_self:?}"
+		);
+		let task = TaskBmc::get(&ctx, &mm, ids[0]).await?;
+		assert_eq!(task.blocker_ids, [ids[1]]);
+
+		// -- Exec & Check - remove
+		TaskBmc::remove_blocker(&ctx, &mm, ids[0], ids[1]).await?;
+		let task = TaskBmc::get(&ctx, &mm, ids[0]).await?;
+		assert!(task.blocker_ids.is_empty());
 
 		// -- Clean
-		TaskBmc::delete(&ctx, &mm, id).await?;
+		for id in ids {
+			TaskBmc::purge(&ctx, &mm, id).await?;
+		}
 
 		Ok(())
 	}
 
 	#[serial]
 	#[tokio::test]
-	async fn test_get_err_not_found() -> Result<()> {
+	async fn test_assign_and_list_assigned_to_me_ok() -> Result<()> {
 		// -- Setup & Fixtures
 		let mm = _dev_utils::init_test().await;
-		let ctx = Ctx::root_ctx();
-		let fx_id = 100;
+		let root_ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_ctx = Ctx::new(1000)?.with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_title = "test_assign_and_list_assigned_to_me_ok - task 01";
+		let id = TaskBmc::create(
+			&root_ctx,
+			&mm,
+			TaskForCreate {
+				title: fx_title.to_string(),
+				..Default::default()
+			},
+		)
+		.await?;
+		let fx_filter: TaskFilter = serde_json::from_value(serde_json::json!({
+			"assigned_to_me": true
+		}))?;
 
 		// -- Exec
-		let res = TaskBmc::get(&ctx, &mm, fx_id).await;
+		let res = TaskBmc::assign(&root_ctx, &mm, id, Some(9999)).await;
+		TaskBmc::assign(&root_ctx, &mm, id, Some(fx_ctx.user_id())).await?;
+		let tasks = TaskBmc::list(&fx_ctx, &mm, Some(fx_filter), None).await?;
 
 		// -- Check
 		assert!(
-			matches!(res, Err(Error::EntityNotFound { entity: "task", id: 100 })),
-			"EntityNotFound not matching"
+			matches!(res, Err(Error::EntityNotFound { entity: "user", id: 9999 })),
+			"Assignee should exist. Actual: {res:?}"
+		);
+		assert_eq!(tasks.len(), 1);
+		assert_eq!(tasks[0].title, fx_title);
+		assert_eq!(tasks[0].assignee_id, Some(fx_ctx.user_id()));
+
+		// -- Exec & Check - unassign
+		TaskBmc::assign(&root_ctx, &mm, id, None).await?;
+		let res = TaskBmc::get(&fx_ctx, &mm, id).await;
+		assert!(
+			matches!(res, Err(Error::EntityNotFound { entity: "task", .. })),
+			"Unassigned user should not access the task. Actual: {res:?}"
 		);
 
+		// -- Clean
+		TaskBmc::purge(&root_ctx, &mm, id).await?;
+
 		Ok(())
 	}
 
 	#[serial]
 	#[tokio::test]
-	async fn test_list_ok() -> Result<()> {
+	async fn test_search_ok() -> Result<()> {
 		// -- Setup & Fixtures
 		let mm = _dev_utils::init_test().await;
-		let ctx = Ctx::root_ctx();
-		let fx_titles = &["test_list_ok 01", "test_list_ok 02"];
-		_dev_utils::seed_tasks(&ctx, &mm, fx_titles).await?;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_tasks = &[
+			(
+				"test_search_ok - task 01",
+				Some("Check the <i>quasarflux</i> fuel & level"),
+			),
+			("test_search_ok - quasarflux launch", None),
+			("test_search_ok - task 03", Some("Nothing to see")),
+		];
+		let mut ids = Vec::new();
+		for (title, description) in fx_tasks {
+			let task_c = TaskForCreate {
+				title: title.to_string(),
+				description: description.map(|d| d.to_string()),
+				..Default::default()
+			};
+			ids.push(TaskBmc::create(&ctx, &mm, task_c).await?);
+		}
+		let fx_list_options: ListOptions =
+			serde_json::from_value(serde_json::json!({"limit": 1}))?;
 
 		// -- Exec
-		let tasks = TaskBmc::list(&ctx, &mm).await?;
+		let page = TaskBmc::search(&ctx, &mm, "quasarflux", None, None).await?;
+		let first_page =
+			TaskBmc::search(&ctx, &mm, "quasarflux", None, Some(fx_list_options))
+				.await?;
+		let next_list_options = ListOptions {
+			limit: Some(1),
+			cursor: first_page.next_cursor.clone(),
+			..Default::default()
+		};
+		let next_page =
+			TaskBmc::search(&ctx, &mm, "quasarflux", None, Some(next_list_options))
+				.await?;
 
 		// -- Check
-		let tasks: Vec<Task> = tasks
-			.into_iter()
-			.filter(|t| t.title.starts_with("test_list_ok"))
-			.collect();
-		assert_eq!(tasks.len(), 2, "number of seeded tasks.");
+		// Note: Title matches ranked before description matches.
+		let hit_ids: Vec<i64> = page.data.iter().map(|h| h.entity.id).collect();
+		assert_eq!(hit_ids, [ids[1], ids[0]]);
+		assert!(page.data[0].rank > page.data[1].rank);
+		assert!(
+			page.data[1].snippet.contains(
+				"&lt;i&gt;<mark>quasarflux</mark>&lt;/i&gt; fuel &amp; level"
+			),
+			"Snippet should be escaped and highlight the match. Actual: {}",
+			page.data[1].snippet
+		);
+		assert_eq!(first_page.data[0].entity.id, ids[1]);
+		assert_eq!(next_page.data[0].entity.id, ids[0]);
+		assert!(next_page.next_cursor.is_none());
 
 		// -- Clean
-		for task in tasks.iter() {
-			TaskBmc::delete(&ctx, &mm, task.id).await?;
+		for id in ids {
+			TaskBmc::purge(&ctx, &mm, id).await?;
 		}
 
 		Ok(())
@@ -154,48 +2113,228 @@ mod tests {
 
 	#[serial]
 	#[tokio::test]
-	async fn test_update_ok() -> Result<()> {
+	async fn test_move_to_position_ok() -> Result<()> {
 		// -- Setup & Fixtures
 		let mm = _dev_utils::init_test().await;
-		let ctx = Ctx::root_ctx();
-		let fx_title = "test_update_ok - task 01";
-		let fx_title_new = "test_update_ok - task 01 - new";
-		let fx_task = _dev_utils::seed_tasks(&ctx, &mm, &[fx_title])
-			.await?
-			.remove(0);
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_prefix = "test_move_to_position_ok";
+		let mut ids = Vec::new();
+		for i in 0..3 {
+			let task_c = TaskForCreate {
+				title: format!("{fx_prefix} - task {i}"),
+				..Default::default()
+			};
+			ids.push(TaskBmc::create(&ctx, &mm, task_c).await?);
+		}
+		let list_ids = || async {
+			let filter: TaskFilter = serde_json::from_value(serde_json::json!({
+				"title": {"$starts_with": fx_prefix}
+			}))?;
+			let list_options: ListOptions = serde_json::from_value(
+				serde_json::json!({"order_bys": ["position", "id"]}),
+			)?;
+			let tasks =
+				TaskBmc::list(&ctx, &mm, Some(filter), Some(list_options)).await?;
+			let ids: Vec<i64> = tasks.into_iter().map(|t| t.id).collect();
+			anyhow::Ok(ids)
+		};
+		assert_eq!(list_ids().await?, [ids[0], ids[1], ids[2]]);
 
-		// -- Exec
-		TaskBmc::update(
+		// -- Exec & Check
+		TaskBmc::move_to_position(
 			&ctx,
 			&mm,
-			fx_task.id,
-			TaskForUpdate { title: Some(fx_title_new.to_string()) },
+			ids[2],
+			TaskPositionTarget::Before(ids[0]),
+		)
+		.await?;
+		assert_eq!(list_ids().await?, [ids[2], ids[0], ids[1]]);
+
+		TaskBmc::move_to_position(
+			&ctx,
+			&mm,
+			ids[2],
+			TaskPositionTarget::After(ids[0]),
+		)
+		.await?;
+		assert_eq!(list_ids().await?, [ids[0], ids[2], ids[1]]);
+
+		TaskBmc::move_to_position(
+			&ctx,
+			&mm,
+			ids[0],
+			TaskPositionTarget::After(ids[1]),
 		)
 		.await?;
+		assert_eq!(list_ids().await?, [ids[2], ids[1], ids[0]]);
+
+		// -- Clean
+		for id in ids {
+			TaskBmc::purge(&ctx, &mm, id).await?;
+		}
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_create_concurrent_position_unique_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_title = "test_create_concurrent_position_unique_ok";
+		let fx_task_c = || TaskForCreate {
+			title: fx_title.to_string(),
+			..Default::default()
+		};
+
+		// -- Exec
+		let (id_01, id_02, id_03) = tokio::try_join!(
+			TaskBmc::create(&ctx, &mm, fx_task_c()),
+			TaskBmc::create(&ctx, &mm, fx_task_c()),
+			TaskBmc::create(&ctx, &mm, fx_task_c()),
+		)?;
 
 		// -- Check
-		let task = TaskBmc::get(&ctx, &mm, fx_task.id).await?;
-		assert_eq!(task.title, fx_title_new);
+		let ids = [id_01, id_02, id_03];
+		let mut positions = Vec::new();
+		for id in ids {
+			positions.push(TaskBmc::get(&ctx, &mm, id).await?.position);
+		}
+		positions.sort();
+		positions.dedup();
+		assert_eq!(positions.len(), 3, "Positions should be unique");
+
+		// -- Clean
+		for id in ids {
+			TaskBmc::purge(&ctx, &mm, id).await?;
+		}
 
 		Ok(())
 	}
 
 	#[serial]
 	#[tokio::test]
-	async fn test_delete_err_not_found() -> Result<()> {
+	async fn test_update_done_next_occurrence_ok() -> Result<()> {
 		// -- Setup & Fixtures
 		let mm = _dev_utils::init_test().await;
-		let ctx = Ctx::root_ctx();
-		let fx_id = 100;
+		let ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_title = "test_update_done_next_occurrence_ok - task 01";
+		let fx_due_at = parse_utc("2024-01-31T09:00:00Z")?;
+		let id = TaskBmc::create(
+			&ctx,
+			&mm,
+			TaskForCreate {
+				title: fx_title.to_string(),
+				due_at: Some(fx_due_at),
+				recurrence: Some("RRULE:COUNT=2;FREQ=DAILY".to_string()),
+				..Default::default()
+			},
+		)
+		.await?;
+		let fx_task_done = || TaskForUpdate {
+			status: Some(TaskStatus::Done),
+			..Default::default()
+		};
 
 		// -- Exec
-		let res = TaskBmc::delete(&ctx, &mm, fx_id).await;
+		TaskBmc::update(&ctx, &mm, id, fx_task_done()).await?;
+		let filter: TaskFilter = serde_json::from_value(serde_json::json!({
+			"title": {"$eq": fx_title},
+			"id": {"$not": id}
+		}))?;
+		let next_tasks = TaskBmc::list(&ctx, &mm, Some(filter), None).await?;
+		// Last occurrence (COUNT=1) done, and done again.
+		let next_id = next_tasks.first().map(|t| t.id).context("No next task")?;
+		TaskBmc::update(&ctx, &mm, next_id, fx_task_done()).await?;
+		TaskBmc::update(&ctx, &mm, id, fx_task_done()).await?;
 
 		// -- Check
+		assert_eq!(next_tasks.len(), 1);
+		let next_task = &next_tasks[0];
+		assert_eq!(next_task.status, TaskStatus::Todo);
+		assert_eq!(
+			format_time(next_task.due_at.context("No due_at")?),
+			"2024-02-01T09:00:00Z"
+		);
+		assert_eq!(next_task.recurrence.as_deref(), Some("FREQ=DAILY;COUNT=1"));
+		let task = TaskBmc::get(&ctx, &mm, id).await?;
 		assert!(
-			matches!(res, Err(Error::EntityNotFound { entity: "task", id: 100 })),
-			"EntityNotFound not matching"
+			task.recurrence.is_none(),
+			"Rule should move to the next task"
 		);
+		let count = TaskBmc::count(
+			&ctx,
+			&mm,
+			Some(serde_json::from_value(serde_json::json!({
+				"title": {"$eq": fx_title}
+			}))?),
+		)
+		.await?;
+		assert_eq!(count, 2, "No other occurrence should be created");
+
+		// -- Clean
+		TaskBmc::purge(&ctx, &mm, id).await?;
+		TaskBmc::purge(&ctx, &mm, next_id).await?;
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_update_done_by_assignee_next_occurrence_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let root_ctx = Ctx::root_ctx().with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_ctx_assignee = Ctx::new(1000)?.with_workspace_id(DEMO_WORKSPACE_ID);
+		let fx_title = "test_update_done_by_assignee_next_occurrence_ok - task 01";
+		// Note: The project is not accessible to the assignee (creator only).
+		let fx_project_id = ProjectBmc::create(
+			&root_ctx,
+			&mm,
+			ProjectForCreate {
+				name: fx_title.to_string(),
+				description: None,
+			},
+		)
+		.await?;
+		let id = TaskBmc::create(
+			&root_ctx,
+			&mm,
+			TaskForCreate {
+				project_id: Some(fx_project_id),
+				title: fx_title.to_string(),
+				recurrence: Some("FREQ=WEEKLY".to_string()),
+				..Default::default()
+			},
+		)
+		.await?;
+		TaskBmc::assign(&root_ctx, &mm, id, Some(fx_ctx_assignee.user_id())).await?;
+
+		// -- Exec
+		let task_u = TaskForUpdate {
+			status: Some(TaskStatus::Done),
+			..Default::default()
+		};
+		TaskBmc::update(&fx_ctx_assignee, &mm, id, task_u).await?;
+
+		// -- Check
+		let filter: TaskFilter = serde_json::from_value(serde_json::json!({
+			"title": {"$eq": fx_title},
+			"id": {"$not": id}
+		}))?;
+		let next_tasks =
+			TaskBmc::list(&fx_ctx_assignee, &mm, Some(filter), None).await?;
+		assert_eq!(next_tasks.len(), 1);
+		let next_task = &next_tasks[0];
+		assert_eq!(next_task.cid, root_ctx.user_id(), "Should keep the creator");
+		assert_eq!(next_task.assignee_id, Some(fx_ctx_assignee.user_id()));
+		assert_eq!(next_task.project_id, Some(fx_project_id));
+
+		// -- Clean
+		TaskBmc::purge(&root_ctx, &mm, id).await?;
+		TaskBmc::purge(&root_ctx, &mm, next_task.id).await?;
+		ProjectBmc::delete(&root_ctx, &mm, fx_project_id).await?;
 
 		Ok(())
 	}
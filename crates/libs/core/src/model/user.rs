@@ -1,7 +1,9 @@
 use crate::ctx::Ctx;
 use crate::model::base::{self, DbBmc};
+use crate::model::store::dbx;
 use crate::model::{Error, ModelManager, Result};
 use crate::pwd::{self, EncryptContent};
+use lib_base::time::now_utc;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use sqlb::{Fields, HasFields};
@@ -18,6 +20,11 @@ pub struct User {
 	pub id: i64,
 	pub username: String,
 
+	// -- Email
+	pub email: Option<String>,
+	#[serde_as(as = "Option<Rfc3339>")]
+	pub email_verified_at: Option<OffsetDateTime>,
+
 	// -- Timestamps
 	pub cid: i64,
 	#[serde_as(as = "Rfc3339")]
@@ -31,11 +38,14 @@ pub struct User {
 pub struct UserForCreate {
 	pub username: String,
 	pub pwd_clear: String,
+	/// Not verified (see `UserBmc::verify_email`).
+	pub email: Option<String>,
 }
 
 #[derive(Fields)]
 struct UserForInsert {
 	username: String,
+	email: Option<String>,
 }
 
 #[derive(Clone, FromRow, Fields, Debug)]
@@ -47,6 +57,10 @@ pub struct UserForLogin {
 	pub pwd: Option<String>, // encrypted, #_scheme_id_#....
 	pub pwd_salt: Uuid,
 	pub token_salt: Uuid,
+
+	// -- email info
+	pub email: Option<String>,
+	pub email_verified_at: Option<OffsetDateTime>,
 }
 
 #[derive(Clone, FromRow, Fields, Debug)]
@@ -72,22 +86,43 @@ pub struct UserBmc;
 impl DbBmc for UserBmc {
 	const TABLE: &'static str = "user";
 	const HAS_TIMESTAMPS: bool = true;
+	const AUDIT_EXCLUDED_COLUMNS: &'static [&'static str] =
+		&["pwd", "pwd_salt", "token_salt"];
 }
 
 impl UserBmc {
-	#[allow(unused)]
+	/// Create the user with its pwd.
+	///
+	/// Note: The pwd policy is for the caller to validate (see `pwd::validate_pwd_policy`).
 	pub async fn create(
 		ctx: &Ctx,
 		mm: &ModelManager,
 		user_c: UserForCreate,
 	) -> Result<i64> {
-		let UserForCreate { username, pwd_clear } = user_c;
-
-		let user_fi = UserForInsert { username: username.to_string() };
-
-		let user_id = base::create::<Self, _>(ctx, mm, user_fi).await.map_err(
+		let UserForCreate { username, pwd_clear, email } = user_c;
+
+		// Note: Surrounding whitespace is ignored, as for `user.username_norm`.
+		let username = username.trim().to_string();
+		if !is_valid_username(&username) {
+			return Err(Error::UserUsernameInvalid { username });
+		}
+		let email = email.map(|email| email.trim().to_lowercase());
+		if let Some(email) = email.as_ref().filter(|e| !is_valid_email(e)) {
+			return Err(Error::UserEmailInvalid { email: email.to_string() });
+		}
+
+		let user_fi = UserForInsert {
+			username: username.to_string(),
+			email: email.clone(),
+		};
+
+		// -- Insert user and pwd in one transaction.
+		//    (rolled back on drop if not committed)
+		let mm = mm.begin().await?;
+
+		let user_id = base::create::<Self, _>(ctx, &mm, user_fi).await.map_err(
 			|model_error| match model_error {
-				Error::Sqlx(sqlx_error) => {
+				Error::Dbx(dbx::Error::Sqlx(sqlx_error)) => {
 					if let Some((code, constraint)) =
 						sqlx_error.as_database_error().and_then(|db_error| {
 							db_error.code().zip(db_error.constraint())
@@ -99,14 +134,21 @@ impl UserBmc {
 						{
 							return Error::UserAlreadyExists { username };
 						}
+						if code == "23505" && constraint == "user_email_key" {
+							return Error::UserEmailAlreadyExists {
+								email: email.unwrap_or_default(),
+							};
+						}
 					}
-					Error::Sqlx(sqlx_error)
+					Error::Dbx(dbx::Error::Sqlx(sqlx_error))
 				}
 				_ => model_error,
 			},
 		)?;
 
-		Self::update_pwd(ctx, mm, user_id, &pwd_clear).await?;
+		Self::update_pwd(ctx, &mm, user_id, &pwd_clear).await?;
+
+		mm.commit().await?;
 
 		Ok(user_id)
 	}
@@ -126,13 +168,12 @@ impl UserBmc {
 	where
 		E: UserBy,
 	{
-		let db = mm.db();
+		let dbx = mm.dbx();
 
-		let user = sqlb::select()
+		let sb = sqlb::select()
 			.table(Self::TABLE)
-			.and_where("username", "=", username)
-			.fetch_optional::<_, E>(db)
-			.await?;
+			.and_where("username", "=", username);
+		let user = dbx.fetch_optional::<_, E>(&sb).await?;
 
 		Ok(user)
 	}
@@ -143,7 +184,7 @@ impl UserBmc {
 		id: i64,
 		pwd_clear: &str,
 	) -> Result<()> {
-		let db = mm.db();
+		let dbx = mm.dbx();
 
 		let user: UserForLogin = Self::get(ctx, mm, id).await?;
 		let pwd = pwd::encrypt_pwd(&EncryptContent {
@@ -151,17 +192,83 @@ impl UserBmc {
 			salt: user.pwd_salt.to_string(),
 		})?;
 
-		sqlb::update()
+		let sb = sqlb::update()
 			.table(Self::TABLE)
 			.and_where("id", "=", id)
-			.data(vec![("pwd", pwd.to_string()).into()])
-			.exec(db)
-			.await?;
+			.data(vec![("pwd", pwd.to_string()).into()]);
+		dbx.exec(&sb).await?;
+
+		Ok(())
+	}
+
+	/// Mark the user email as verified (see `email_verified_at`).
+	pub async fn verify_email(_ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+		let dbx = mm.dbx();
+
+		let sb = sqlb::update()
+			.table(Self::TABLE)
+			.and_where("id", "=", id)
+			.data(vec![("email_verified_at", now_utc()).into()]);
+		let count = dbx.exec(&sb).await?;
+
+		if count == 0 {
+			return Err(Error::EntityNotFound { entity: Self::TABLE, id });
+		}
+
+		Ok(())
+	}
+
+	/// Regenerate the user `token_salt`, so that all the issued web tokens
+	/// stop validating (i.e., all the user sessions are ended).
+	pub async fn update_token_salt(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		id: i64,
+	) -> Result<()> {
+		let dbx = mm.dbx();
+
+		let sb = sqlb::update()
+			.table(Self::TABLE)
+			.and_where("id", "=", id)
+			.data(vec![("token_salt", Uuid::new_v4()).into()]);
+		let count = dbx.exec(&sb).await?;
+
+		if count == 0 {
+			return Err(Error::EntityNotFound { entity: Self::TABLE, id });
+		}
 
 		Ok(())
 	}
 }
 
+/// The username length bounds (in chars, see `user.username`).
+const USERNAME_MIN_LEN: usize = 3;
+const USERNAME_MAX_LEN: usize = 128;
+
+/// Letters, digits, and `.`, `_`, `-`, `+`, `@` (so that emails are valid usernames).
+fn is_valid_username(username: &str) -> bool {
+	let len = username.chars().count();
+
+	(USERNAME_MIN_LEN..=USERNAME_MAX_LEN).contains(&len)
+		&& username
+			.chars()
+			.all(|c| c.is_alphanumeric() || matches!(c, '.' | '_' | '-' | '+' | '@'))
+}
+
+/// The email length bound (in chars, see `user.email`).
+const EMAIL_MAX_LEN: usize = 256;
+
+/// Note: Only a sanity check (`local@domain`), the email verification being the real one.
+fn is_valid_email(email: &str) -> bool {
+	let valid_parts = email.split_once('@').is_some_and(|(local, domain)| {
+		!local.is_empty() && domain.contains('.') && !domain.contains('@')
+	});
+
+	valid_parts
+		&& email.chars().count() <= EMAIL_MAX_LEN
+		&& !email.chars().any(|c| c.is_whitespace() || c.is_control())
+}
+
 // region:    --- Tests
 #[cfg(test)]
 mod tests {
@@ -230,6 +337,7 @@ mod tests {
 			UserForCreate {
 				username: fx_username.to_string(),
 				pwd_clear: fx_pwd_clear.to_string(),
+				email: None,
 			},
 		)
 		.await?;
@@ -267,6 +375,7 @@ mod tests {
 			UserForCreate {
 				username: fx_username_01.to_string(),
 				pwd_clear: fx_pwd_clear.to_string(),
+				email: None,
 			},
 		)
 		.await?;
@@ -277,6 +386,7 @@ mod tests {
 			UserForCreate {
 				username: fx_username_02.to_string(),
 				pwd_clear: fx_pwd_clear.to_string(),
+				email: None,
 			},
 		)
 		.await;
@@ -288,10 +398,7 @@ mod tests {
 		);
 
 		// -- Clean
-		sqlx::query(r#"DELETE FROM "user" where id = $1"#)
-			.bind(id)
-			.execute(mm.db())
-			.await?;
+		base::delete::<UserBmc>(&ctx, &mm, id).await?;
 
 		Ok(())
 	}
@@ -313,6 +420,7 @@ mod tests {
 			UserForCreate {
 				username: fx_username_01.to_string(),
 				pwd_clear: fx_pwd_clear.to_string(),
+				email: None,
 			},
 		)
 		.await?;
@@ -323,21 +431,167 @@ mod tests {
 			UserForCreate {
 				username: fx_username_02.to_string(),
 				pwd_clear: fx_pwd_clear.to_string(),
+				email: None,
 			},
 		)
 		.await;
 
 		// -- Check
 		assert!(
-			matches!(&res, Err(Error::UserAlreadyExists { username: ref s }) if s == fx_username_02),
+			matches!(&res, Err(Error::UserAlreadyExists { username: ref s }) if s == fx_username_02.trim()),
 			"res not matching expected Error::UserAlreadyExists. res: {res:?}"
 		);
 
 		// -- Clean
-		sqlx::query(r#"DELETE FROM "user" where id = $1"#)
-			.bind(id)
-			.execute(mm.db())
-			.await?;
+		base::delete::<UserBmc>(&ctx, &mm, id).await?;
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_create_trim_username_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx();
+		let fx_username = " demo4 ";
+
+		// -- Exec
+		let id = UserBmc::create(
+			&ctx,
+			&mm,
+			UserForCreate {
+				username: fx_username.to_string(),
+				pwd_clear: "welcome4".to_string(),
+				email: None,
+			},
+		)
+		.await?;
+
+		// -- Check
+		let user: User = UserBmc::get(&ctx, &mm, id).await?;
+		assert_eq!(user.username, "demo4");
+
+		// -- Clean
+		base::delete::<UserBmc>(&ctx, &mm, id).await?;
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_create_verify_email_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx();
+		let fx_username = "demo5";
+		let fx_email = " Demo5@Example.com ";
+
+		// -- Exec
+		let id = UserBmc::create(
+			&ctx,
+			&mm,
+			UserForCreate {
+				username: fx_username.to_string(),
+				pwd_clear: "welcome5".to_string(),
+				email: Some(fx_email.to_string()),
+			},
+		)
+		.await?;
+		let user_created: UserForLogin = UserBmc::get(&ctx, &mm, id).await?;
+		UserBmc::verify_email(&ctx, &mm, id).await?;
+		let user_verified: UserForLogin = UserBmc::get(&ctx, &mm, id).await?;
+
+		// -- Check
+		assert_eq!(user_created.email.as_deref(), Some("demo5@example.com"));
+		assert!(user_created.email_verified_at.is_none());
+		assert!(user_verified.email_verified_at.is_some());
+
+		// -- Clean
+		base::delete::<UserBmc>(&ctx, &mm, id).await?;
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_update_token_salt_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx();
+		let fx_username = "demo1";
+		let user: UserForAuth = UserBmc::first_by_username(&ctx, &mm, fx_username)
+			.await?
+			.context("Should have user 'demo1'")?;
+
+		// -- Exec
+		UserBmc::update_token_salt(&ctx, &mm, user.id).await?;
+
+		// -- Check
+		let user_after: UserForAuth = UserBmc::get(&ctx, &mm, user.id).await?;
+		assert_ne!(user_after.token_salt, user.token_salt);
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_create_err_username_invalid() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx();
+		let fx_usernames = &["ab", "demo 4", "demo4<script>"];
+
+		for fx_username in fx_usernames {
+			// -- Exec
+			let res = UserBmc::create(
+				&ctx,
+				&mm,
+				UserForCreate {
+					username: fx_username.to_string(),
+					pwd_clear: "welcome4".to_string(),
+					email: None,
+				},
+			)
+			.await;
+
+			// -- Check
+			assert!(
+				matches!(&res, Err(Error::UserUsernameInvalid { .. })),
+				"'{fx_username}' should be invalid. Actual: {res:?}"
+			);
+		}
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_create_err_email_invalid() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let ctx = Ctx::root_ctx();
+		let fx_emails = &["demo6", "demo6@localhost", "demo 6@example.com"];
+
+		for fx_email in fx_emails {
+			// -- Exec
+			let res = UserBmc::create(
+				&ctx,
+				&mm,
+				UserForCreate {
+					username: "demo6".to_string(),
+					pwd_clear: "welcome6".to_string(),
+					email: Some(fx_email.to_string()),
+				},
+			)
+			.await;
+
+			// -- Check
+			assert!(
+				matches!(&res, Err(Error::UserEmailInvalid { .. })),
+				"'{fx_email}' should be invalid. Actual: {res:?}"
+			);
+		}
 
 		Ok(())
 	}
@@ -0,0 +1,199 @@
+//! Workspace (i.e., tenant) model.
+//!
+//! - The `DbBmc::WORKSPACE_SCOPED` entities (e.g., `Task`) belong to a workspace,
+//!   and are scoped to the `Ctx` workspace by `model::base`.
+//! - A user can only access the workspaces they are member of (`workspace_member`).
+//!
+
+use crate::ctx::Ctx;
+use crate::model::base::{self, DbBmc};
+use crate::model::{Error, ModelManager, Result};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use sqlb::Fields;
+use sqlx::FromRow;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+// region:    --- Workspace Types
+#[serde_as]
+#[derive(Clone, Fields, FromRow, Debug, Serialize)]
+pub struct Workspace {
+	pub id: i64,
+
+	pub name: String,
+
+	// -- Timestamps
+	pub cid: i64,
+	#[serde_as(as = "Rfc3339")]
+	pub ctime: OffsetDateTime,
+	pub mid: i64,
+	#[serde_as(as = "Rfc3339")]
+	pub mtime: OffsetDateTime,
+}
+
+#[derive(Deserialize, Fields)]
+pub struct WorkspaceForCreate {
+	pub name: String,
+}
+// endregion: --- Workspace Types
+
+pub struct WorkspaceBmc;
+
+impl DbBmc for WorkspaceBmc {
+	const TABLE: &'static str = "workspace";
+	const HAS_TIMESTAMPS: bool = true;
+}
+
+const MEMBER_TABLE: &str = "workspace_member";
+
+impl WorkspaceBmc {
+	/// Create the workspace, with the ctx user as its first member.
+	pub async fn create(
+		ctx: &Ctx,
+		mm: &ModelManager,
+		workspace_c: WorkspaceForCreate,
+	) -> Result<i64> {
+		let mm = mm.begin().await?;
+
+		let id = base::create::<Self, _>(ctx, &mm, workspace_c).await?;
+		if !ctx.is_root() {
+			Self::add_member(ctx, &mm, id, ctx.user_id()).await?;
+		}
+
+		mm.commit().await?;
+
+		Ok(id)
+	}
+
+	/// Get the workspace (only if the ctx user is a member).
+	pub async fn get(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Workspace> {
+		if !ctx.is_root() && !Self::is_member(ctx, mm, id, ctx.user_id()).await? {
+			return Err(Error::EntityNotFound { entity: Self::TABLE, id });
+		}
+
+		base::get::<Self, _>(ctx, mm, id).await
+	}
+
+	/// List the workspaces the ctx user is a member of (all for the root ctx).
+	pub async fn list(ctx: &Ctx, mm: &ModelManager) -> Result<Vec<Workspace>> {
+		let dbx = mm.dbx();
+
+		let query = if ctx.is_root() {
+			sqlx::query_as(r#"SELECT * FROM workspace ORDER BY id"#)
+		} else {
+			sqlx::query_as(
+				r#"SELECT workspace.* FROM workspace
+				   JOIN workspace_member ON workspace_member.workspace_id = workspace.id
+				   WHERE workspace_member.user_id = $1
+				   ORDER BY workspace.id"#,
+			)
+			.bind(ctx.user_id())
+		};
+		let workspaces = dbx.fetch_all_as(query).await?;
+
+		Ok(workspaces)
+	}
+
+	pub async fn add_member(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		id: i64,
+		user_id: i64,
+	) -> Result<()> {
+		let dbx = mm.dbx();
+
+		let sb = sqlb::insert().table(MEMBER_TABLE).data(vec![
+			("workspace_id", id).into(),
+			("user_id", user_id).into(),
+		]);
+		dbx.exec(&sb).await?;
+
+		Ok(())
+	}
+
+	pub async fn is_member(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		id: i64,
+		user_id: i64,
+	) -> Result<bool> {
+		let dbx = mm.dbx();
+
+		let sb = sqlb::select()
+			.table(MEMBER_TABLE)
+			.columns(&["count(*)"])
+			.and_where("workspace_id", "=", id)
+			.and_where("user_id", "=", user_id);
+		let (count,) = dbx.fetch_one::<_, (i64,)>(&sb).await?;
+
+		Ok(count > 0)
+	}
+
+	/// The default workspace of a user, i.e., the first one they are member of.
+	pub async fn first_id_by_member(
+		_ctx: &Ctx,
+		mm: &ModelManager,
+		user_id: i64,
+	) -> Result<Option<i64>> {
+		let dbx = mm.dbx();
+
+		let sb = sqlb::select()
+			.table(MEMBER_TABLE)
+			.columns(&["workspace_id"])
+			.and_where("user_id", "=", user_id)
+			.order_by("workspace_id")
+			.limit(1);
+		let id = dbx.fetch_optional::<_, (i64,)>(&sb).await?;
+
+		Ok(id.map(|(id,)| id))
+	}
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::_dev_utils::{self, DEMO_WORKSPACE_ID};
+	use anyhow::Result;
+	use serial_test::serial;
+
+	#[serial]
+	#[tokio::test]
+	async fn test_create_ok_member() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let fx_ctx = Ctx::new(1000)?;
+		let fx_ctx_other = Ctx::new(1001)?;
+		let fx_name = "test_create_ok_member - workspace 01";
+
+		// -- Exec
+		let id = WorkspaceBmc::create(
+			&fx_ctx,
+			&mm,
+			WorkspaceForCreate { name: fx_name.to_string() },
+		)
+		.await?;
+
+		// -- Check
+		let workspace = WorkspaceBmc::get(&fx_ctx, &mm, id).await?;
+		assert_eq!(workspace.name, fx_name);
+		let ids: Vec<i64> = WorkspaceBmc::list(&fx_ctx, &mm)
+			.await?
+			.into_iter()
+			.map(|w| w.id)
+			.collect();
+		assert_eq!(ids, [DEMO_WORKSPACE_ID, id]);
+		let res = WorkspaceBmc::get(&fx_ctx_other, &mm, id).await;
+		assert!(
+			matches!(res, Err(Error::EntityNotFound { entity: "workspace", .. })),
+			"Non member should not get the workspace. Actual: {res:?}"
+		);
+
+		// -- Clean
+		base::purge::<WorkspaceBmc>(&Ctx::root_ctx(), &mm, id).await?;
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
@@ -11,6 +11,8 @@ pub enum Error {
 
 	// -- Pwd
 	PwdNotMatching,
+	PwdTooShort { min_len: usize },
+	PwdTooLong { max_len: usize },
 	SchemeUnknown(String),
 	SchemeNotFoundInContent,
 }
@@ -14,6 +14,10 @@ use lazy_regex::regex_captures;
 
 const DEFAULT_SCHEME: &str = Scheme01::NAME;
 
+/// The clear pwd length bounds (in chars) of `validate_pwd_policy`.
+const PWD_MIN_LEN: usize = 8;
+const PWD_MAX_LEN: usize = 128;
+
 // region:    --- Types
 
 pub struct EncryptContent {
@@ -61,6 +65,21 @@ pub fn validate_pwd(
 	}
 }
 
+/// Validate that a new clear pwd (e.g., on register) follows the pwd policy.
+///
+/// Note: Not applied to the existing pwds (e.g., on login).
+pub fn validate_pwd_policy(pwd_clear: &str) -> Result<()> {
+	let len = pwd_clear.chars().count();
+
+	if len < PWD_MIN_LEN {
+		Err(Error::PwdTooShort { min_len: PWD_MIN_LEN })
+	} else if len > PWD_MAX_LEN {
+		Err(Error::PwdTooLong { max_len: PWD_MAX_LEN })
+	} else {
+		Ok(())
+	}
+}
+
 // endregion: --- Public Functions
 
 // region:    --- Scheme Infra
@@ -115,6 +134,22 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_validate_pwd_policy() -> Result<()> {
+		// -- Exec & Check
+		validate_pwd_policy("welcome-01")?;
+		assert!(matches!(
+			validate_pwd_policy("welcome"),
+			Err(Error::PwdTooShort { .. })
+		));
+		assert!(matches!(
+			validate_pwd_policy(&"x".repeat(PWD_MAX_LEN + 1)),
+			Err(Error::PwdTooLong { .. })
+		));
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_extract_scheme_ok() -> Result<()> {
 		// -- Fixtures
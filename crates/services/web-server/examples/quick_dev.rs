@@ -25,7 +25,9 @@ async fn main() -> Result<()> {
 			"method": "create_task",
 			"params": {
 				"data": {
-					"title": "task AAA"
+					"title": "task AAA",
+					"priority": 1,
+					"due_at": "2030-01-01T00:00:00Z"
 				}
 			}
 		}),
@@ -40,7 +42,8 @@ async fn main() -> Result<()> {
 			"params": {
 				"id": 1000, // Hardcode the task id.
 				"data": {
-					"title": "task BB"
+					"title": "task BB",
+					"status": "in_progress"
 				}
 			}
 		}),
@@ -59,11 +62,32 @@ async fn main() -> Result<()> {
 	);
 	req_delete_task.await?.print().await?;
 
+	let req_restore_task = hc.do_post(
+		"/api/rpc",
+		json!({
+			"id": 1,
+			"method": "restore_task",
+			"params": {
+				"id": 1001 // Harcode the task id
+			}
+		}),
+	);
+	req_restore_task.await?.print().await?;
+
 	let req_list_tasks = hc.do_post(
 		"/api/rpc",
 		json!({
 			"id": 1,
-			"method": "list_tasks"
+			"method": "list_tasks",
+			"params": {
+				"filters": {
+					"title": {"$starts_with": "task"}
+				},
+				"list_options": {
+					"order_bys": ["!id"],
+					"limit": 10
+				}
+			}
 		}),
 	);
 	req_list_tasks.await?.print().await?;
@@ -4,19 +4,24 @@
 
 mod error;
 mod log;
+mod notify;
 mod web;
 
 pub use self::error::{Error, Result};
 pub use lib_core::config;
 
+use crate::notify::{LogNotifier, Notifier};
 use crate::web::mw_auth::{mw_ctx_require, mw_ctx_resolve};
 use crate::web::mw_req_stamp::mw_req_stamp;
 use crate::web::mw_res_map::mw_response_map;
-use crate::web::{routes_login, routes_static, rpc};
+use crate::web::{
+	routes_attachment, routes_login, routes_pwd_reset, routes_static, rpc,
+};
 use axum::{middleware, Router};
 use lib_core::_dev_utils;
 use lib_core::model::ModelManager;
 use std::net::SocketAddr;
+use std::sync::Arc;
 use tower_cookies::CookieManagerLayer;
 use tracing::info;
 use tracing_subscriber::EnvFilter;
@@ -37,13 +42,18 @@ async fn main() -> Result<()> {
 	// -- Initialize ModelController
 	let mm = ModelManager::new().await?;
 
+	// -- Initialize Notifier (logs only, FOR DEV ONLY)
+	let notifier: Arc<dyn Notifier> = Arc::new(LogNotifier);
+
 	// -- Define Routes
-	let routes_rpc =
-		rpc::routes(mm.clone()).route_layer(middleware::from_fn(mw_ctx_require));
+	let routes_api = rpc::routes(mm.clone())
+		.merge(routes_attachment::routes(mm.clone()))
+		.route_layer(middleware::from_fn(mw_ctx_require));
 
 	let routes_all = Router::new()
-		.merge(routes_login::routes(mm.clone()))
-		.nest("/api", routes_rpc)
+		.merge(routes_login::routes(mm.clone(), notifier.clone()))
+		.merge(routes_pwd_reset::routes(mm.clone(), notifier))
+		.nest("/api", routes_api)
 		.layer(middleware::map_response(mw_response_map))
 		.layer(middleware::from_fn_with_state(mm.clone(), mw_ctx_resolve))
 		.layer(middleware::from_fn(mw_req_stamp))
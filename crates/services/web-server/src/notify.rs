@@ -0,0 +1,89 @@
+//! `Notifier` - The user notification delivery (e.g., password reset tokens).
+//!
+//! Design:
+//!
+//! - The `Notifier` trait is the pluggable delivery channel (e.g., email, sms), so the web
+//!   handlers only build the `Notification` and do not know how it reaches the user.
+//! - The `LogNotifier` (default) only logs the notifications, for local development.
+//!
+
+// region:    --- Modules
+
+use async_trait::async_trait;
+use serde::Serialize;
+use tracing::info;
+
+// endregion: --- Modules
+
+// region:    --- Notifier
+
+#[derive(Debug)]
+pub enum Notification {
+	PwdReset {
+		username: String,
+		email: String,
+		token: String,
+	},
+	EmailVerify {
+		username: String,
+		email: String,
+		token: String,
+	},
+}
+
+#[async_trait]
+pub trait Notifier: Send + Sync {
+	async fn notify(&self, notification: Notification) -> Result<()>;
+}
+
+// endregion: --- Notifier
+
+// region:    --- LogNotifier
+
+/// Logs the notifications (tokens included), so FOR DEV ONLY.
+pub struct LogNotifier;
+
+#[async_trait]
+impl Notifier for LogNotifier {
+	async fn notify(&self, notification: Notification) -> Result<()> {
+		match notification {
+			Notification::PwdReset { username, email, token } => info!(
+				"{:<12} - pwd reset for '{username}' <{email}> - token: {token}",
+				"NOTIFY"
+			),
+			Notification::EmailVerify { username, email, token } => info!(
+				"{:<12} - email verify for '{username}' <{email}> - token: {token}",
+				"NOTIFY"
+			),
+		}
+
+		Ok(())
+	}
+}
+
+// endregion: --- LogNotifier
+
+// region:    --- Error
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug, Serialize)]
+#[allow(dead_code)] // Only constructed by the delivering notifiers (e.g., email).
+pub enum Error {
+	SendFail(String),
+}
+
+// region:    --- Error Boilerplate
+impl core::fmt::Display for Error {
+	fn fmt(
+		&self,
+		fmt: &mut core::fmt::Formatter,
+	) -> core::result::Result<(), core::fmt::Error> {
+		write!(fmt, "{self:?}")
+	}
+}
+
+impl std::error::Error for Error {}
+// endregion: --- Error Boilerplate
+
+// endregion: --- Error
@@ -0,0 +1,24 @@
+use crate::web::Result;
+use lib_base::token::{generate_token, validate_token, Token};
+use lib_core::config;
+
+/// Note: The token salt is the email to verify, so that the token becomes
+///       invalid if the user email changes.
+pub fn generate_email_verify_token(username: &str, email: &str) -> Result<Token> {
+	let config = &config();
+	let token = generate_token(
+		username,
+		config.EMAIL_VERIFY_DURATION_SEC,
+		email,
+		&config.TOKEN_KEY,
+	)?;
+
+	Ok(token)
+}
+
+pub fn validate_email_verify_token(origin_token: &Token, email: &str) -> Result<()> {
+	let config = &config();
+	validate_token(origin_token, email, &config.TOKEN_KEY)?;
+
+	Ok(())
+}
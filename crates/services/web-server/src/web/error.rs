@@ -1,8 +1,9 @@
-use crate::web;
+use crate::{notify, web};
+use axum::extract::multipart::MultipartError;
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use lib_base::token;
-use lib_core::{model, pwd};
+use lib_core::{ctx, model, pwd};
 use serde::Serialize;
 use tracing::debug;
 
@@ -13,13 +14,50 @@ pub type Result<T> = core::result::Result<T, Error>;
 pub enum Error {
 	// -- RPC
 	RpcMethodUnknown(String),
-	RpcMissingParams { rpc_method: String },
-	RpcFailJsonParams { rpc_method: String },
+	RpcMissingParams {
+		rpc_method: String,
+	},
+	RpcFailJsonParams {
+		rpc_method: String,
+	},
+	RpcAccessDenied {
+		rpc_method: String,
+		permission: &'static str,
+	},
+
+	// -- Attachment
+	AttachmentAccessDenied {
+		permission: &'static str,
+	},
+	AttachmentFileMissing,
+	AttachmentMultipart(String),
+
+	// -- Register
+	RegisterDisabled,
+	RegisterEmailMissing,
+
+	// -- Email Verify
+	EmailVerifyTokenInvalid(token::Error),
+
+	// -- Change Pwd
+	ChangePwdFailPwdNotMatching {
+		user_id: i64,
+	},
+
+	// -- Pwd Reset
+	PwdResetTokenInvalid(token::Error),
 
 	// -- Login
 	LoginFailUsernameNotFound,
-	LoginFailUserHasNoPwd { user_id: i64 },
-	LoginFailPwdNotMatching { user_id: i64 },
+	LoginFailUserHasNoPwd {
+		user_id: i64,
+	},
+	LoginFailPwdNotMatching {
+		user_id: i64,
+	},
+	LoginFailEmailNotVerified {
+		user_id: i64,
+	},
 
 	// -- Middelware/Extractor
 	ReqStampNotInResponseExt,
@@ -28,15 +66,23 @@ pub enum Error {
 	CtxExt(web::mw_auth::CtxExtError),
 
 	// -- Modules
+	Ctx(ctx::Error),
 	Model(model::Error),
 	Pwd(pwd::Error),
 	Token(token::Error),
+	Notify(notify::Error),
 
 	// -- External Modules
 	SerdeJson(String),
 }
 
 // region:    --- Error Froms
+impl From<ctx::Error> for Error {
+	fn from(val: ctx::Error) -> Self {
+		Self::Ctx(val)
+	}
+}
+
 impl From<model::Error> for Error {
 	fn from(val: model::Error) -> Self {
 		Error::Model(val)
@@ -55,6 +101,18 @@ impl From<token::Error> for Error {
 	}
 }
 
+impl From<notify::Error> for Error {
+	fn from(val: notify::Error) -> Self {
+		Self::Notify(val)
+	}
+}
+
+impl From<MultipartError> for Error {
+	fn from(val: MultipartError) -> Self {
+		Error::AttachmentMultipart(val.to_string())
+	}
+}
+
 impl From<serde_json::Error> for Error {
 	fn from(val: serde_json::Error) -> Self {
 		Error::SerdeJson(val.to_string())
@@ -104,8 +162,42 @@ impl Error {
 			| LoginFailPwdNotMatching { .. } => {
 				(StatusCode::FORBIDDEN, ClientError::LOGIN_FAIL)
 			}
+			LoginFailEmailNotVerified { .. } => {
+				(StatusCode::FORBIDDEN, ClientError::EMAIL_NOT_VERIFIED)
+			}
+
+			// -- Register
+			RegisterDisabled => {
+				(StatusCode::FORBIDDEN, ClientError::REGISTER_DISABLED)
+			}
+			RegisterEmailMissing => {
+				(StatusCode::BAD_REQUEST, ClientError::INVALID_EMAIL)
+			}
+
+			// -- Email Verify
+			EmailVerifyTokenInvalid(_) => (
+				StatusCode::BAD_REQUEST,
+				ClientError::EMAIL_VERIFY_TOKEN_INVALID,
+			),
+			Pwd(pwd::Error::PwdTooShort { .. } | pwd::Error::PwdTooLong { .. }) => {
+				(StatusCode::BAD_REQUEST, ClientError::PWD_POLICY_NOT_MET)
+			}
+
+			// -- Change Pwd
+			ChangePwdFailPwdNotMatching { .. } => {
+				(StatusCode::FORBIDDEN, ClientError::CHANGE_PWD_FAIL)
+			}
+
+			// -- Pwd Reset
+			PwdResetTokenInvalid(_) => (
+				StatusCode::BAD_REQUEST,
+				ClientError::PWD_RESET_TOKEN_INVALID,
+			),
 
 			// -- Auth
+			CtxExt(web::mw_auth::CtxExtError::WorkspaceNotMember) => {
+				(StatusCode::FORBIDDEN, ClientError::WORKSPACE_NOT_MEMBER)
+			}
 			CtxExt(_) => (StatusCode::FORBIDDEN, ClientError::NO_AUTH),
 
 			// -- Model
@@ -113,9 +205,80 @@ impl Error {
 				StatusCode::BAD_REQUEST,
 				ClientError::ENTITY_NOT_FOUND { entity, id: *id },
 			),
+			Model(model::Error::VersionConflict { entity, id, .. }) => (
+				StatusCode::CONFLICT,
+				ClientError::VERSION_CONFLICT { entity, id: *id },
+			),
+			Model(
+				model::Error::TaskParentCycle { id, .. }
+				| model::Error::TaskBlockerCycle { id, .. },
+			) => (StatusCode::BAD_REQUEST, ClientError::TASK_CYCLE { id: *id }),
+			Model(model::Error::Position(_)) => {
+				(StatusCode::BAD_REQUEST, ClientError::INVALID_POSITION)
+			}
+			Model(model::Error::Recurrence(_)) => {
+				(StatusCode::BAD_REQUEST, ClientError::INVALID_RECURRENCE)
+			}
+			Model(model::Error::TaskAssigneeNotMember { .. }) => {
+				(StatusCode::BAD_REQUEST, ClientError::ASSIGNEE_NOT_MEMBER)
+			}
+			Model(model::Error::LabelAlreadyExists { .. }) => {
+				(StatusCode::BAD_REQUEST, ClientError::LABEL_ALREADY_EXISTS)
+			}
+			Model(model::Error::CommentNotAuthor { .. }) => {
+				(StatusCode::FORBIDDEN, ClientError::ACCESS_DENIED)
+			}
+			Model(model::Error::AttachmentTooLarge { max_size, .. }) => (
+				StatusCode::PAYLOAD_TOO_LARGE,
+				ClientError::ATTACHMENT_TOO_LARGE { max_size: *max_size },
+			),
+			Model(model::Error::AttachmentFileNameInvalid(_)) => {
+				(StatusCode::BAD_REQUEST, ClientError::INVALID_PARAMS)
+			}
+			Model(model::Error::WorkspaceNotInCtx { .. }) => {
+				(StatusCode::BAD_REQUEST, ClientError::WORKSPACE_REQUIRED)
+			}
+			Model(model::Error::Pwd(
+				pwd::Error::PwdTooShort { .. } | pwd::Error::PwdTooLong { .. },
+			)) => (StatusCode::BAD_REQUEST, ClientError::PWD_POLICY_NOT_MET),
 			Model(model::Error::UserAlreadyExists { .. }) => {
 				(StatusCode::BAD_REQUEST, ClientError::USER_ALREADY_EXISTS)
 			}
+			Model(model::Error::UserUsernameInvalid { .. }) => {
+				(StatusCode::BAD_REQUEST, ClientError::INVALID_USERNAME)
+			}
+			Model(model::Error::UserEmailInvalid { .. }) => {
+				(StatusCode::BAD_REQUEST, ClientError::INVALID_EMAIL)
+			}
+			Model(model::Error::UserEmailAlreadyExists { .. }) => {
+				(StatusCode::BAD_REQUEST, ClientError::EMAIL_ALREADY_EXISTS)
+			}
+			Model(
+				model::Error::ListLimitOverMax { .. }
+				| model::Error::ListOrderByUnknown(_)
+				| model::Error::ListCursorInvalid(_)
+				| model::Error::ListCursorOrderByNotId
+				| model::Error::ListCursorWithOffset,
+			) => (StatusCode::BAD_REQUEST, ClientError::INVALID_LIST_OPTIONS),
+
+			// -- Rpc
+			RpcMethodUnknown(_) => {
+				(StatusCode::BAD_REQUEST, ClientError::RPC_METHOD_UNKNOWN)
+			}
+			RpcMissingParams { .. } | RpcFailJsonParams { .. } => {
+				(StatusCode::BAD_REQUEST, ClientError::INVALID_PARAMS)
+			}
+			RpcAccessDenied { .. } => {
+				(StatusCode::FORBIDDEN, ClientError::ACCESS_DENIED)
+			}
+
+			// -- Attachment
+			AttachmentAccessDenied { .. } => {
+				(StatusCode::FORBIDDEN, ClientError::ACCESS_DENIED)
+			}
+			AttachmentFileMissing | AttachmentMultipart(_) => {
+				(StatusCode::BAD_REQUEST, ClientError::INVALID_PARAMS)
+			}
 
 			// -- Fallback
 			_ => (
@@ -134,8 +297,30 @@ impl Error {
 #[allow(non_camel_case_types)]
 pub enum ClientError {
 	LOGIN_FAIL,
+	EMAIL_NOT_VERIFIED,
+	EMAIL_VERIFY_TOKEN_INVALID,
+	INVALID_EMAIL,
+	EMAIL_ALREADY_EXISTS,
+	REGISTER_DISABLED,
+	INVALID_USERNAME,
+	PWD_POLICY_NOT_MET,
+	PWD_RESET_TOKEN_INVALID,
+	CHANGE_PWD_FAIL,
 	NO_AUTH,
+	WORKSPACE_NOT_MEMBER,
+	ACCESS_DENIED,
 	ENTITY_NOT_FOUND { entity: &'static str, id: i64 },
+	VERSION_CONFLICT { entity: &'static str, id: i64 },
+	TASK_CYCLE { id: i64 },
+	INVALID_POSITION,
+	ASSIGNEE_NOT_MEMBER,
+	INVALID_RECURRENCE,
+	LABEL_ALREADY_EXISTS,
+	ATTACHMENT_TOO_LARGE { max_size: usize },
 	USER_ALREADY_EXISTS,
+	WORKSPACE_REQUIRED,
+	RPC_METHOD_UNKNOWN,
+	INVALID_PARAMS,
+	INVALID_LIST_OPTIONS,
 	SERVICE_ERROR,
 }
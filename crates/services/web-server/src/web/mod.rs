@@ -1,10 +1,14 @@
 // region:    --- Modules
 
+mod email_verify_token;
 mod error;
 pub mod mw_auth;
 pub mod mw_req_stamp;
 pub mod mw_res_map;
+mod pwd_reset_token;
+pub mod routes_attachment;
 pub mod routes_login;
+pub mod routes_pwd_reset;
 pub mod routes_static;
 pub mod rpc;
 mod web_token;
@@ -21,8 +25,13 @@ use uuid::Uuid;
 
 pub const AUTH_TOKEN: &str = "auth-token";
 
-fn set_token_cookie(cookies: &Cookies, user: &str, salt: &str) -> Result<()> {
-	let token = generate_web_token(user, salt)?;
+/// Optional request header selecting the active workspace
+/// (defaults to the user first workspace).
+pub const WORKSPACE_HEADER: &str = "x-workspace-id";
+
+/// Note: The token ident is the session ident (see `SessionBmc`).
+fn set_token_cookie(cookies: &Cookies, ident: &str, salt: &str) -> Result<()> {
+	let token = generate_web_token(ident, salt)?;
 
 	let mut cookie = Cookie::new(AUTH_TOKEN, token.to_string());
 	cookie.set_http_only(true);
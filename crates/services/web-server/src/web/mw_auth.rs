@@ -1,20 +1,24 @@
 use crate::web::set_token_cookie;
 use crate::web::web_token::validate_web_token;
-use crate::web::AUTH_TOKEN;
 use crate::web::{Error, Result};
+use crate::web::{ReqStamp, AUTH_TOKEN, WORKSPACE_HEADER};
 use async_trait::async_trait;
 use axum::extract::{FromRequestParts, State};
 use axum::http::request::Parts;
-use axum::http::Request;
+use axum::http::{HeaderMap, Request};
 use axum::middleware::Next;
 use axum::response::Response;
 use lib_base::token::Token;
 use lib_core::ctx::Ctx;
+use lib_core::model::role::RoleBmc;
+use lib_core::model::session::SessionBmc;
 use lib_core::model::user::{UserBmc, UserForAuth};
+use lib_core::model::workspace::WorkspaceBmc;
 use lib_core::model::ModelManager;
 use serde::Serialize;
 use tower_cookies::{Cookie, Cookies};
 use tracing::debug;
+use uuid::Uuid;
 
 pub async fn mw_ctx_require<B>(
 	ctx: Result<CtxW>,
@@ -36,11 +40,22 @@ pub async fn mw_ctx_resolve<B>(
 ) -> Result<Response> {
 	debug!("{:<12} - mw_ctx_resolve", "MIDDLEWARE");
 
-	let ctx_ext_result = _ctx_resolve(mm, &cookies).await;
+	let ctx_ext_result =
+		_ctx_resolve(mm, &cookies, req.headers())
+			.await
+			.map(|CtxW(ctx)| match req.extensions().get::<ReqStamp>() {
+				Some(req_stamp) => CtxW(ctx.with_req_uuid(req_stamp.uuid)),
+				None => CtxW(ctx),
+			});
 
+	// Note: Workspace errors do not invalidate the auth token.
 	if ctx_ext_result.is_err()
-		&& !matches!(ctx_ext_result, Err(CtxExtError::TokenNotInCookie))
-	{
+		&& !matches!(
+			ctx_ext_result,
+			Err(CtxExtError::TokenNotInCookie
+				| CtxExtError::WorkspaceIdWrongFormat
+				| CtxExtError::WorkspaceNotMember)
+		) {
 		cookies.remove(Cookie::named(AUTH_TOKEN))
 	}
 
@@ -51,7 +66,11 @@ pub async fn mw_ctx_resolve<B>(
 	Ok(next.run(req).await)
 }
 
-async fn _ctx_resolve(mm: State<ModelManager>, cookies: &Cookies) -> CtxExtResult {
+async fn _ctx_resolve(
+	mm: State<ModelManager>,
+	cookies: &Cookies,
+	headers: &HeaderMap,
+) -> CtxExtResult {
 	// -- Get Token String
 	let token = cookies
 		.get(AUTH_TOKEN)
@@ -60,26 +79,74 @@ async fn _ctx_resolve(mm: State<ModelManager>, cookies: &Cookies) -> CtxExtResul
 
 	// -- Parse Token
 	let token: Token = token.parse().map_err(|_| CtxExtError::TokenWrongFormat)?;
-
-	// -- Get UserForAuth
-	let user: UserForAuth =
-		UserBmc::first_by_username(&Ctx::root_ctx(), &mm, &token.ident)
-			.await
-			.map_err(|ex| CtxExtError::ModelAccessError(ex.to_string()))?
-			.ok_or(CtxExtError::UserNotFound)?;
+	let session_ident: Uuid = token
+		.ident
+		.parse()
+		.map_err(|_| CtxExtError::TokenWrongFormat)?;
+
+	// -- Get Session & UserForAuth
+	// Note: A revoked session (i.e., deleted) does not validate anymore.
+	let root_ctx = Ctx::root_ctx();
+	let session = SessionBmc::first_by_ident(&root_ctx, &mm, session_ident)
+		.await
+		.map_err(|ex| CtxExtError::ModelAccessError(ex.to_string()))?
+		.ok_or(CtxExtError::SessionNotFound)?;
+	let user: UserForAuth = UserBmc::get(&root_ctx, &mm, session.user_id)
+		.await
+		.map_err(|_| CtxExtError::UserNotFound)?;
 
 	// -- Validate Token
 	validate_web_token(&token, &user.token_salt.to_string())
 		.map_err(|_| CtxExtError::FailValidate)?;
 
-	// -- Update Token
-	set_token_cookie(cookies, &user.username, &user.token_salt.to_string())
+	// -- Update Token & Session
+	set_token_cookie(cookies, &token.ident, &user.token_salt.to_string())
 		.map_err(|_| CtxExtError::CannotSetTokenCookie)?;
+	SessionBmc::update_last_seen(&root_ctx, &mm, &session)
+		.await
+		.map_err(|ex| CtxExtError::ModelAccessError(ex.to_string()))?;
+
+	// -- Get Roles & Permissions
+	let roles = RoleBmc::list_names_by_user(&root_ctx, &mm, user.id)
+		.await
+		.map_err(|ex| CtxExtError::ModelAccessError(ex.to_string()))?;
+	let permissions = RoleBmc::list_permissions_by_user(&root_ctx, &mm, user.id)
+		.await
+		.map_err(|ex| CtxExtError::ModelAccessError(ex.to_string()))?;
+
+	// -- Get Workspace
+	let workspace_id = match headers.get(WORKSPACE_HEADER) {
+		Some(workspace_id) => {
+			let workspace_id: i64 = workspace_id
+				.to_str()
+				.ok()
+				.and_then(|v| v.parse().ok())
+				.ok_or(CtxExtError::WorkspaceIdWrongFormat)?;
+			let is_member =
+				WorkspaceBmc::is_member(&root_ctx, &mm, workspace_id, user.id)
+					.await
+					.map_err(|ex| CtxExtError::ModelAccessError(ex.to_string()))?;
+			if !is_member {
+				return Err(CtxExtError::WorkspaceNotMember);
+			}
+			Some(workspace_id)
+		}
+		None => WorkspaceBmc::first_id_by_member(&root_ctx, &mm, user.id)
+			.await
+			.map_err(|ex| CtxExtError::ModelAccessError(ex.to_string()))?,
+	};
 
 	// -- Create CtxExtResult
-	Ctx::new(user.id)
-		.map(CtxW)
-		.map_err(|ex| CtxExtError::CtxCreateFail(ex.to_string()))
+	let ctx = Ctx::new(user.id)
+		.map_err(|ex| CtxExtError::CtxCreateFail(ex.to_string()))?
+		.with_session_id(session.id)
+		.with_rbac(roles, permissions);
+	let ctx = match workspace_id {
+		Some(workspace_id) => ctx.with_workspace_id(workspace_id),
+		None => ctx,
+	};
+
+	Ok(CtxW(ctx))
 }
 
 // region:    --- Ctx Extractor
@@ -113,12 +180,66 @@ pub enum CtxExtError {
 	TokenNotInCookie,
 	TokenWrongFormat,
 
+	SessionNotFound,
 	UserNotFound,
 	ModelAccessError(String),
 	FailValidate,
 	CannotSetTokenCookie,
 
+	WorkspaceIdWrongFormat,
+	WorkspaceNotMember,
+
 	CtxNotInRequestExt,
 	CtxCreateFail(String),
 }
 // endregion: --- Ctx Extractor Result/Error
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::web::web_token::generate_web_token;
+	use anyhow::Result;
+	use lib_core::_dev_utils;
+	use lib_core::model::session::SessionForCreate;
+	use serial_test::serial;
+
+	#[serial]
+	#[tokio::test]
+	async fn test_ctx_resolve_revoked_session_err() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let root_ctx = Ctx::root_ctx();
+		let user: UserForAuth = UserBmc::first_by_username(&root_ctx, &mm, "demo1")
+			.await?
+			.ok_or(anyhow::anyhow!("demo1 should exist"))?;
+		let ctx = Ctx::new(user.id)?;
+		let session_c = SessionForCreate { user_agent: None };
+		let session_id = SessionBmc::create(&ctx, &mm, session_c).await?;
+		let session = SessionBmc::get(&ctx, &mm, session_id).await?;
+		let token = generate_web_token(
+			&session.ident.to_string(),
+			&user.token_salt.to_string(),
+		)?;
+		let cookies = Cookies::default();
+		cookies.add(Cookie::new(AUTH_TOKEN, token.to_string()));
+		let headers = HeaderMap::new();
+		let CtxW(resolved_ctx) = _ctx_resolve(State(mm.clone()), &cookies, &headers)
+			.await
+			.map_err(Error::CtxExt)?;
+		assert_eq!(resolved_ctx.session_id(), Some(session_id));
+
+		// -- Exec
+		SessionBmc::delete(&ctx, &mm, session_id).await?;
+		let res = _ctx_resolve(State(mm.clone()), &cookies, &headers).await;
+
+		// -- Check
+		assert!(
+			matches!(res, Err(CtxExtError::SessionNotFound)),
+			"Revoked session should not validate. Actual: {res:?}"
+		);
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
@@ -0,0 +1,107 @@
+use crate::web::Result;
+use lib_base::token::{generate_token, validate_token, Token};
+use lib_core::config;
+use lib_core::model::user::UserForLogin;
+
+/// Note: The token salt includes the user encrypted `pwd`, so that the token becomes
+///       invalid once the pwd is updated (i.e., single-use).
+pub fn generate_pwd_reset_token(user: &UserForLogin) -> Result<Token> {
+	let config = &config();
+	let token = generate_token(
+		&user.username,
+		config.PWD_RESET_DURATION_SEC,
+		&pwd_reset_salt(user),
+		&config.TOKEN_KEY,
+	)?;
+
+	Ok(token)
+}
+
+pub fn validate_pwd_reset_token(
+	origin_token: &Token,
+	user: &UserForLogin,
+) -> Result<()> {
+	let config = &config();
+	validate_token(origin_token, &pwd_reset_salt(user), &config.TOKEN_KEY)?;
+
+	Ok(())
+}
+
+fn pwd_reset_salt(user: &UserForLogin) -> String {
+	format!(
+		"{}.{}",
+		user.token_salt,
+		user.pwd.as_deref().unwrap_or_default()
+	)
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::web::Error;
+	use anyhow::Result;
+	use lib_base::token;
+	use std::thread;
+	use std::time::Duration;
+	use uuid::Uuid;
+
+	fn fx_user() -> UserForLogin {
+		UserForLogin {
+			id: 1000,
+			username: "user_one".to_string(),
+			pwd: Some("#01#encrypted-pwd".to_string()),
+			pwd_salt: Uuid::new_v4(),
+			token_salt: Uuid::new_v4(),
+			email: None,
+			email_verified_at: None,
+		}
+	}
+
+	#[test]
+	fn test_validate_single_use_err() -> Result<()> {
+		// -- Setup & Fixtures
+		let mut fx_user = fx_user();
+		let fx_token = generate_pwd_reset_token(&fx_user)?;
+		validate_pwd_reset_token(&fx_token, &fx_user)?;
+
+		// -- Exec
+		// Note: As after the pwd update of the reset confirm.
+		fx_user.pwd = Some("#01#encrypted-new-pwd".to_string());
+		let res = validate_pwd_reset_token(&fx_token, &fx_user);
+
+		// -- Check
+		assert!(
+			matches!(res, Err(Error::Token(token::Error::SignatureNotMatching))),
+			"Should not validate once the pwd is updated. Actual: {res:?}"
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_validate_expired_err() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_user = fx_user();
+		let fx_duration_sec = 0.01; // 10ms
+		let fx_token = token::generate_token(
+			&fx_user.username,
+			fx_duration_sec,
+			&pwd_reset_salt(&fx_user),
+			&config().TOKEN_KEY,
+		)?;
+
+		// -- Exec
+		thread::sleep(Duration::from_millis(20));
+		let res = validate_pwd_reset_token(&fx_token, &fx_user);
+
+		// -- Check
+		assert!(
+			matches!(res, Err(Error::Token(token::Error::Expired))),
+			"Should be expired. Actual: {res:?}"
+		);
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
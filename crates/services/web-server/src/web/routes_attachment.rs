@@ -0,0 +1,146 @@
+use crate::config;
+use crate::web::mw_auth::CtxW;
+use crate::web::{Error, Result};
+use axum::body::StreamBody;
+use axum::extract::{DefaultBodyLimit, Multipart, Path, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use lib_core::ctx::Ctx;
+use lib_core::model::attachment::{AttachmentBmc, AttachmentForCreate};
+use lib_core::model::ModelManager;
+use serde_json::{json, Value};
+use tokio_util::io::ReaderStream;
+use tracing::debug;
+
+/// The multipart field of the uploaded file.
+const FILE_FIELD: &str = "file";
+
+pub fn routes(mm: ModelManager) -> Router {
+	Router::new()
+		.route(
+			"/tasks/:task_id/attachments",
+			// Note: The size limit is checked while reading the file field.
+			post(upload_handler).layer(DefaultBodyLimit::disable()),
+		)
+		.route("/attachments/:id", get(download_handler))
+		.with_state(mm)
+}
+
+// region:    --- Upload
+async fn upload_handler(
+	State(mm): State<ModelManager>,
+	CtxW(ctx): CtxW,
+	Path(task_id): Path<i64>,
+	mut multipart: Multipart,
+) -> Result<Json<Value>> {
+	debug!("{:<12} - upload_handler", "HANDLER");
+
+	check_permission(&ctx, "attachment:create")?;
+
+	let max_size = config().ATTACHMENT_MAX_SIZE;
+
+	while let Some(mut field) = multipart.next_field().await? {
+		if field.name() != Some(FILE_FIELD) {
+			continue;
+		}
+		let file_name = field.file_name().unwrap_or_default().to_string();
+
+		let mut content = Vec::new();
+		while let Some(chunk) = field.chunk().await? {
+			content.extend_from_slice(&chunk);
+			// Note: No need to read further, `AttachmentBmc::create` rejects it.
+			if content.len() > max_size {
+				break;
+			}
+		}
+
+		let attachment_c = AttachmentForCreate { task_id, file_name };
+		let id = AttachmentBmc::create(&ctx, &mm, attachment_c, &content).await?;
+		let attachment = AttachmentBmc::get(&ctx, &mm, id).await?;
+
+		return Ok(Json(json!({
+			"result": {
+				"data": attachment
+			}
+		})));
+	}
+
+	Err(Error::AttachmentFileMissing)
+}
+// endregion: --- Upload
+
+// region:    --- Download
+async fn download_handler(
+	State(mm): State<ModelManager>,
+	CtxW(ctx): CtxW,
+	Path(id): Path<i64>,
+) -> Result<Response> {
+	debug!("{:<12} - download_handler", "HANDLER");
+
+	check_permission(&ctx, "attachment:read")?;
+
+	let (attachment, reader) = AttachmentBmc::open(&ctx, &mm, id).await?;
+
+	let headers = [
+		(header::CONTENT_TYPE, attachment.content_type),
+		(header::CONTENT_LENGTH, attachment.size.to_string()),
+		(
+			header::CONTENT_DISPOSITION,
+			content_disposition(&attachment.file_name),
+		),
+		(header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+	];
+	let body = StreamBody::new(ReaderStream::new(reader));
+
+	Ok((headers, body).into_response())
+}
+
+/// The attachment `Content-Disposition`, with the ASCII `filename` fallback
+/// and the UTF-8 `filename*` (RFC 6266 and RFC 5987).
+fn content_disposition(file_name: &str) -> String {
+	// Note: Quotes and backslashes would end the quoted file name early.
+	let ascii_name: String = file_name
+		.chars()
+		.map(|c| match c {
+			'"' | '\\' => '_',
+			c if c.is_ascii() && !c.is_ascii_control() => c,
+			_ => '_',
+		})
+		.collect();
+
+	// The RFC 5987 `attr-char`s are kept, all the other bytes are percent-encoded.
+	let utf8_name: String = file_name
+		.bytes()
+		.map(|b| match b {
+			b'a'..=b'z'
+			| b'A'..=b'Z'
+			| b'0'..=b'9'
+			| b'!'
+			| b'#'
+			| b'$'
+			| b'&'
+			| b'+'
+			| b'-'
+			| b'.'
+			| b'^'
+			| b'_'
+			| b'`'
+			| b'|'
+			| b'~' => (b as char).to_string(),
+			b => format!("%{b:02X}"),
+		})
+		.collect();
+
+	format!("attachment; filename=\"{ascii_name}\"; filename*=UTF-8''{utf8_name}")
+}
+// endregion: --- Download
+
+fn check_permission(ctx: &Ctx, permission: &'static str) -> Result<()> {
+	if ctx.has_permission(permission) {
+		Ok(())
+	} else {
+		Err(Error::AttachmentAccessDenied { permission })
+	}
+}
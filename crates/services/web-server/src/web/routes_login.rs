@@ -1,29 +1,87 @@
+use crate::config;
+use crate::notify::{Notification, Notifier};
+use crate::web::email_verify_token::{
+	generate_email_verify_token, validate_email_verify_token,
+};
+use crate::web::mw_auth::CtxW;
 use crate::web::{self, remove_token_cookie};
 use crate::web::{Error, Result};
-use axum::extract::State;
+use axum::extract::{FromRef, State};
+use axum::http::{header, HeaderMap};
 use axum::routing::post;
 use axum::{Json, Router};
+use lib_base::token::Token;
 use lib_core::ctx::Ctx;
-use lib_core::model::user::{UserBmc, UserForLogin};
+use lib_core::model::role::RoleBmc;
+use lib_core::model::session::{SessionBmc, SessionForCreate};
+use lib_core::model::user::{UserBmc, UserForAuth, UserForCreate, UserForLogin};
+use lib_core::model::workspace::{WorkspaceBmc, WorkspaceForCreate};
 use lib_core::model::ModelManager;
 use lib_core::pwd::EncryptContent;
 use lib_core::pwd::{self, SchemeStatus};
 use serde::Deserialize;
 use serde_json::{json, Value};
+use std::sync::Arc;
+use time::OffsetDateTime;
 use tower_cookies::Cookies;
-use tracing::debug;
+use tracing::{debug, error};
+
+#[derive(Clone)]
+struct LoginState {
+	mm: ModelManager,
+	notifier: Arc<dyn Notifier>,
+	policy: LoginPolicy,
+}
+
+/// The login and register policy (from the config, but in the state for the tests).
+#[derive(Clone, Copy)]
+struct LoginPolicy {
+	email_verify_required: bool,
+}
+
+impl FromRef<LoginState> for ModelManager {
+	fn from_ref(state: &LoginState) -> Self {
+		state.mm.clone()
+	}
+}
+
+impl FromRef<LoginState> for Arc<dyn Notifier> {
+	fn from_ref(state: &LoginState) -> Self {
+		state.notifier.clone()
+	}
+}
+
+impl FromRef<LoginState> for LoginPolicy {
+	fn from_ref(state: &LoginState) -> Self {
+		state.policy
+	}
+}
+
+pub fn routes(mm: ModelManager, notifier: Arc<dyn Notifier>) -> Router {
+	let policy = LoginPolicy {
+		email_verify_required: config().EMAIL_VERIFY_REQUIRED,
+	};
 
-pub fn routes(mm: ModelManager) -> Router {
 	Router::new()
 		.route("/api/login", post(api_login_handler))
 		.route("/api/logoff", post(api_logoff_handler))
-		.with_state(mm)
+		.route("/api/logoff-all", post(api_logoff_all_handler))
+		.route("/api/change-pwd", post(api_change_pwd_handler))
+		.route("/api/register", post(api_register_handler))
+		.route("/api/verify-email", post(api_verify_email_handler))
+		.route(
+			"/api/verify-email/request",
+			post(api_verify_email_request_handler),
+		)
+		.with_state(LoginState { mm, notifier, policy })
 }
 
 // region:    --- Login
 async fn api_login_handler(
 	State(mm): State<ModelManager>,
+	State(policy): State<LoginPolicy>,
 	cookies: Cookies,
+	headers: HeaderMap,
 	Json(payload): Json<LoginPayload>,
 ) -> Result<Json<Value>> {
 	debug!("{:<12} - api_login_handler", "HANDLER");
@@ -51,14 +109,21 @@ async fn api_login_handler(
 	)
 	.map_err(|_| Error::LoginFailPwdNotMatching { user_id })?;
 
+	// -- Validate the email (when required).
+	validate_email_verified(
+		user_id,
+		user.email_verified_at,
+		policy.email_verify_required,
+	)?;
+
 	// -- If pwd scheme outdated, update pwd.
 	if let SchemeStatus::Outdated = scheme_status {
 		debug!("pwd encrypt scheme outdated, upgrading.");
 		UserBmc::update_pwd(&root_ctx, &mm, user.id, &pwd_clear).await?;
 	}
 
-	// -- Set web token.
-	web::set_token_cookie(&cookies, &user.username, &user.token_salt.to_string())?;
+	// -- Start the session.
+	start_session(&mm, &cookies, &headers, user.id).await?;
 
 	// -- Create the success body.
 	let body = Json(json!({
@@ -75,10 +140,224 @@ struct LoginPayload {
 	username: String,
 	pwd: String,
 }
+
+fn validate_email_verified(
+	user_id: i64,
+	email_verified_at: Option<OffsetDateTime>,
+	email_verify_required: bool,
+) -> Result<()> {
+	if email_verify_required && email_verified_at.is_none() {
+		return Err(Error::LoginFailEmailNotVerified { user_id });
+	}
+
+	Ok(())
+}
 // endregion: --- Login
 
+// region:    --- Register
+
+/// The role of the registered users.
+const REGISTER_ROLE: &str = "member";
+
+async fn api_register_handler(
+	State(mm): State<ModelManager>,
+	State(notifier): State<Arc<dyn Notifier>>,
+	State(policy): State<LoginPolicy>,
+	cookies: Cookies,
+	headers: HeaderMap,
+	Json(payload): Json<RegisterPayload>,
+) -> Result<Json<Value>> {
+	debug!("{:<12} - api_register_handler", "HANDLER");
+
+	if !config().REGISTER_ENABLED {
+		return Err(Error::RegisterDisabled);
+	}
+
+	let RegisterPayload {
+		username,
+		pwd: pwd_clear,
+		email,
+		auto_login,
+	} = payload;
+	let root_ctx = Ctx::root_ctx();
+	let email_verify_required = policy.email_verify_required;
+
+	if email_verify_required && email.is_none() {
+		return Err(Error::RegisterEmailMissing);
+	}
+	pwd::validate_pwd_policy(&pwd_clear)?;
+
+	// -- Create the user with its role and workspace (in one transaction).
+	let (user, workspace_id) = {
+		let mm = mm.begin().await?;
+		let workspace_c =
+			WorkspaceForCreate { name: format!("{username} (personal)") };
+		let user_c = UserForCreate { username, pwd_clear, email };
+		let user_id = UserBmc::create(&root_ctx, &mm, user_c).await?;
+		RoleBmc::add_to_user(&root_ctx, &mm, user_id, REGISTER_ROLE).await?;
+
+		// Note: Created by the user, so that they are its first member.
+		let user_ctx = Ctx::new(user_id)?;
+		let workspace_id = WorkspaceBmc::create(&user_ctx, &mm, workspace_c).await?;
+
+		let user: UserForLogin = UserBmc::get(&root_ctx, &mm, user_id).await?;
+
+		mm.commit().await?;
+		(user, workspace_id)
+	};
+	let user_id = user.id;
+
+	// -- Send the email verification (once the user is committed).
+	// Note: Not failing the registration, since the email verification
+	//       can be requested again (see `api_verify_email_request_handler`).
+	if let Some(email) = user.email {
+		if let Err(err) =
+			send_email_verify(notifier.as_ref(), user.username, email).await
+		{
+			error!("register - email verify not sent for user {user_id}: {err:?}");
+		}
+	}
+
+	// -- Start the session (when auto login, and email verification not required).
+	let logged_in = auto_login && !email_verify_required;
+	if logged_in {
+		start_session(&mm, &cookies, &headers, user_id).await?;
+	}
+
+	// -- Create the success body.
+	let body = Json(json!({
+		"result": {
+			"success": true,
+			"user_id": user_id,
+			"workspace_id": workspace_id,
+			"logged_in": logged_in
+		}
+	}));
+
+	Ok(body)
+}
+
+#[derive(Debug, Deserialize)]
+struct RegisterPayload {
+	username: String,
+	pwd: String,
+	email: Option<String>,
+	#[serde(default = "default_auto_login")]
+	auto_login: bool,
+}
+
+fn default_auto_login() -> bool {
+	true
+}
+// endregion: --- Register
+
+// region:    --- Email Verify
+async fn api_verify_email_handler(
+	State(mm): State<ModelManager>,
+	Json(payload): Json<VerifyEmailPayload>,
+) -> Result<Json<Value>> {
+	debug!("{:<12} - api_verify_email_handler", "HANDLER");
+
+	let VerifyEmailPayload { token } = payload;
+	let root_ctx = Ctx::root_ctx();
+
+	// -- Validate the token against the user current email.
+	let token: Token = token.parse().map_err(Error::EmailVerifyTokenInvalid)?;
+	let user_not_matching =
+		|| Error::EmailVerifyTokenInvalid(lib_base::token::Error::UserNotMatching);
+	let user: UserForLogin =
+		UserBmc::first_by_username(&root_ctx, &mm, &token.ident)
+			.await?
+			.ok_or_else(user_not_matching)?;
+	let email = user.email.as_deref().ok_or_else(user_not_matching)?;
+	validate_email_verify_token(&token, email).map_err(|ex| match ex {
+		Error::Token(token_error) => Error::EmailVerifyTokenInvalid(token_error),
+		ex => ex,
+	})?;
+
+	// -- Verify the email.
+	// Note: Keep the first verification time (e.g., token used twice).
+	if user.email_verified_at.is_none() {
+		UserBmc::verify_email(&root_ctx, &mm, user.id).await?;
+	}
+
+	// -- Create the success body.
+	let body = Json(json!({
+		"result": {
+			"success": true
+		}
+	}));
+
+	Ok(body)
+}
+
+#[derive(Debug, Deserialize)]
+struct VerifyEmailPayload {
+	token: String,
+}
+
+/// Re-send the email verification (e.g., token expired).
+async fn api_verify_email_request_handler(
+	State(mm): State<ModelManager>,
+	State(notifier): State<Arc<dyn Notifier>>,
+	Json(payload): Json<VerifyEmailRequestPayload>,
+) -> Result<Json<Value>> {
+	debug!("{:<12} - api_verify_email_request_handler", "HANDLER");
+
+	let VerifyEmailRequestPayload { username } = payload;
+	let root_ctx = Ctx::root_ctx();
+
+	// -- Notify the user (if any, with an email not verified).
+	// Note: Same response otherwise, to not disclose the usernames.
+	let user: Option<UserForLogin> =
+		UserBmc::first_by_username(&root_ctx, &mm, &username).await?;
+	match user {
+		Some(UserForLogin {
+			username,
+			email: Some(email),
+			email_verified_at: None,
+			..
+		}) => send_email_verify(notifier.as_ref(), username, email).await?,
+		_ => debug!("email verify requested for '{username}', nothing to verify"),
+	}
+
+	// -- Create the success body.
+	let body = Json(json!({
+		"result": {
+			"success": true
+		}
+	}));
+
+	Ok(body)
+}
+
+#[derive(Debug, Deserialize)]
+struct VerifyEmailRequestPayload {
+	username: String,
+}
+
+async fn send_email_verify(
+	notifier: &dyn Notifier,
+	username: String,
+	email: String,
+) -> Result<()> {
+	let token = generate_email_verify_token(&username, &email)?;
+	notifier
+		.notify(Notification::EmailVerify {
+			username,
+			email,
+			token: token.to_string(),
+		})
+		.await?;
+
+	Ok(())
+}
+// endregion: --- Email Verify
+
 // region:    --- Logoff
 async fn api_logoff_handler(
+	State(mm): State<ModelManager>,
+	ctx: Option<CtxW>,
 	cookies: Cookies,
 	Json(payload): Json<LogoffPayload>,
 ) -> Result<Json<Value>> {
@@ -86,6 +365,13 @@ async fn api_logoff_handler(
 	let should_logoff = payload.logoff;
 
 	if should_logoff {
+		// -- End the session (if still valid).
+		if let Some(CtxW(ctx)) = ctx {
+			if let Some(session_id) = ctx.session_id() {
+				SessionBmc::delete(&ctx, &mm, session_id).await?;
+			}
+		}
+
 		remove_token_cookie(&cookies)?;
 	}
 
@@ -103,4 +389,235 @@ async fn api_logoff_handler(
 struct LogoffPayload {
 	logoff: bool,
 }
+
+/// Log off all the user sessions (i.e., all the issued web tokens stop validating).
+async fn api_logoff_all_handler(
+	State(mm): State<ModelManager>,
+	CtxW(ctx): CtxW,
+	cookies: Cookies,
+) -> Result<Json<Value>> {
+	debug!("{:<12} - api_logoff_all_handler", "HANDLER");
+
+	let user_id = ctx.user_id();
+
+	// -- End all the sessions (in one transaction).
+	{
+		let mm = mm.begin().await?;
+		UserBmc::update_token_salt(&ctx, &mm, user_id).await?;
+		SessionBmc::delete_by_user(&ctx, &mm, user_id, None).await?;
+		mm.commit().await?;
+	}
+
+	remove_token_cookie(&cookies)?;
+
+	// Create the success body.
+	let body = Json(json!({
+		"result": {
+			"logged_off": true
+		}
+	}));
+
+	Ok(body)
+}
 // endregion: --- Logoff
+
+// region:    --- Change Pwd
+/// Alias of the `change_pwd` rpc method (see `change_pwd`).
+async fn api_change_pwd_handler(
+	State(mm): State<ModelManager>,
+	CtxW(ctx): CtxW,
+	cookies: Cookies,
+	Json(payload): Json<ChangePwdPayload>,
+) -> Result<Json<Value>> {
+	debug!("{:<12} - api_change_pwd_handler", "HANDLER");
+
+	let ChangePwdPayload { pwd, new_pwd } = payload;
+	change_pwd(&ctx, &mm, &cookies, pwd, new_pwd).await?;
+
+	// -- Create the success body.
+	let body = Json(json!({
+		"result": {
+			"success": true
+		}
+	}));
+
+	Ok(body)
+}
+
+#[derive(Debug, Deserialize)]
+struct ChangePwdPayload {
+	pwd: String,
+	new_pwd: String,
+}
+
+/// Change the ctx user pwd, ending their other sessions.
+///
+/// Note: The caller token is re-issued, since the `token_salt` is regenerated.
+pub(crate) async fn change_pwd(
+	ctx: &Ctx,
+	mm: &ModelManager,
+	cookies: &Cookies,
+	pwd_clear: String,
+	new_pwd: String,
+) -> Result<()> {
+	let user_id = ctx.user_id();
+
+	// -- Validate the current password.
+	let user: UserForLogin = UserBmc::get(ctx, mm, user_id).await?;
+	let Some(pwd) = user.pwd else {
+		return Err(Error::ChangePwdFailPwdNotMatching { user_id });
+	};
+	pwd::validate_pwd(
+		&EncryptContent {
+			salt: user.pwd_salt.to_string(),
+			content: pwd_clear,
+		},
+		&pwd,
+	)
+	.map_err(|_| Error::ChangePwdFailPwdNotMatching { user_id })?;
+
+	// -- Update the pwd and end the other sessions (in one transaction).
+	pwd::validate_pwd_policy(&new_pwd)?;
+	{
+		let mm = mm.begin().await?;
+		UserBmc::update_pwd(ctx, &mm, user_id, &new_pwd).await?;
+		UserBmc::update_token_salt(ctx, &mm, user_id).await?;
+		SessionBmc::delete_by_user(ctx, &mm, user_id, ctx.session_id()).await?;
+		mm.commit().await?;
+	}
+
+	// -- Re-issue the caller token (new `token_salt`).
+	if let Some(session_id) = ctx.session_id() {
+		let root_ctx = Ctx::root_ctx();
+		let session = SessionBmc::get(&root_ctx, mm, session_id).await?;
+		let user: UserForAuth = UserBmc::get(&root_ctx, mm, user_id).await?;
+		web::set_token_cookie(
+			cookies,
+			&session.ident.to_string(),
+			&user.token_salt.to_string(),
+		)?;
+	}
+
+	Ok(())
+}
+// endregion: --- Change Pwd
+
+// region:    --- Session
+
+/// Create a new session for the user, and set its web token.
+async fn start_session(
+	mm: &ModelManager,
+	cookies: &Cookies,
+	headers: &HeaderMap,
+	user_id: i64,
+) -> Result<()> {
+	let ctx = Ctx::new(user_id)?;
+	let root_ctx = Ctx::root_ctx();
+
+	let user_agent = headers
+		.get(header::USER_AGENT)
+		.and_then(|v| v.to_str().ok())
+		.map(|v| v.to_string());
+	let session_id =
+		SessionBmc::create(&ctx, mm, SessionForCreate { user_agent }).await?;
+	let session = SessionBmc::get(&ctx, mm, session_id).await?;
+
+	let user: UserForAuth = UserBmc::get(&root_ctx, mm, user_id).await?;
+	web::set_token_cookie(
+		cookies,
+		&session.ident.to_string(),
+		&user.token_salt.to_string(),
+	)?;
+
+	Ok(())
+}
+// endregion: --- Session
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+	use lib_base::time::now_utc;
+	use lib_core::_dev_utils;
+	use serial_test::serial;
+
+	#[test]
+	fn test_validate_email_verified_err_not_verified() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_user_id = 1000;
+
+		// -- Exec
+		let res = validate_email_verified(fx_user_id, None, true);
+
+		// -- Check
+		assert!(
+			matches!(res, Err(Error::LoginFailEmailNotVerified { user_id: 1000 })),
+			"Should refuse the not verified email. Actual: {res:?}"
+		);
+		validate_email_verified(fx_user_id, None, false)?;
+		validate_email_verified(fx_user_id, Some(now_utc()), true)?;
+
+		Ok(())
+	}
+
+	#[serial]
+	#[tokio::test]
+	async fn test_api_login_handler_err_email_not_verified() -> Result<()> {
+		// -- Setup & Fixtures
+		let mm = _dev_utils::init_test().await;
+		let root_ctx = Ctx::root_ctx();
+		let fx_username = "test_api_login_handler_err_email_not_verified";
+		let fx_pwd_clear = "welcome_not_verified";
+		let user_id = UserBmc::create(
+			&root_ctx,
+			&mm,
+			UserForCreate {
+				username: fx_username.to_string(),
+				pwd_clear: fx_pwd_clear.to_string(),
+				email: Some("not_verified@example.com".to_string()),
+			},
+		)
+		.await?;
+		let fx_payload = || LoginPayload {
+			username: fx_username.to_string(),
+			pwd: fx_pwd_clear.to_string(),
+		};
+		let fx_policy = LoginPolicy { email_verify_required: true };
+
+		// -- Exec
+		let res = api_login_handler(
+			State(mm.clone()),
+			State(fx_policy),
+			Cookies::default(),
+			HeaderMap::new(),
+			Json(fx_payload()),
+		)
+		.await;
+
+		// -- Check
+		assert!(
+			matches!(res, Err(Error::LoginFailEmailNotVerified { user_id: id }) if id == user_id),
+			"Should refuse the not verified email. Actual: {res:?}"
+		);
+
+		// -- Exec & Check - verified
+		UserBmc::verify_email(&root_ctx, &mm, user_id).await?;
+		let Json(body) = api_login_handler(
+			State(mm.clone()),
+			State(fx_policy),
+			Cookies::default(),
+			HeaderMap::new(),
+			Json(fx_payload()),
+		)
+		.await?;
+		assert_eq!(body["result"]["success"], true);
+
+		// -- Clean
+		let user_ctx = Ctx::new(user_id)?;
+		SessionBmc::delete_by_user(&user_ctx, &mm, user_id, None).await?;
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
@@ -0,0 +1,144 @@
+use crate::notify::{Notification, Notifier};
+use crate::web::pwd_reset_token::{
+	generate_pwd_reset_token, validate_pwd_reset_token,
+};
+use crate::web::{Error, Result};
+use axum::extract::State;
+use axum::routing::post;
+use axum::{Json, Router};
+use lib_base::token::Token;
+use lib_core::ctx::Ctx;
+use lib_core::model::session::SessionBmc;
+use lib_core::model::user::{UserBmc, UserForLogin};
+use lib_core::model::ModelManager;
+use lib_core::pwd;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tracing::debug;
+
+#[derive(Clone)]
+struct PwdResetState {
+	mm: ModelManager,
+	notifier: Arc<dyn Notifier>,
+}
+
+pub fn routes(mm: ModelManager, notifier: Arc<dyn Notifier>) -> Router {
+	Router::new()
+		.route(
+			"/api/pwd-reset/request",
+			post(api_pwd_reset_request_handler),
+		)
+		.route(
+			"/api/pwd-reset/confirm",
+			post(api_pwd_reset_confirm_handler),
+		)
+		.with_state(PwdResetState { mm, notifier })
+}
+
+// region:    --- Request
+async fn api_pwd_reset_request_handler(
+	State(PwdResetState { mm, notifier }): State<PwdResetState>,
+	Json(payload): Json<PwdResetRequestPayload>,
+) -> Result<Json<Value>> {
+	debug!("{:<12} - api_pwd_reset_request_handler", "HANDLER");
+
+	let PwdResetRequestPayload { username } = payload;
+	let root_ctx = Ctx::root_ctx();
+
+	// -- Notify the user at their verified email (if any).
+	// Note: Same response if the user does not exist or has no verified email,
+	//       to not disclose the usernames.
+	let user: Option<UserForLogin> =
+		UserBmc::first_by_username(&root_ctx, &mm, &username).await?;
+	match user {
+		Some(user) => {
+			// Note: Only to a verified email (i.e., one the user proved to own).
+			let email = user
+				.email
+				.clone()
+				.filter(|_| user.email_verified_at.is_some());
+			match email {
+				Some(email) => {
+					let token = generate_pwd_reset_token(&user)?;
+					notifier
+						.notify(Notification::PwdReset {
+							username: user.username,
+							email,
+							token: token.to_string(),
+						})
+						.await?;
+				}
+				None => debug!(
+					"pwd reset requested for '{username}' without verified email"
+				),
+			}
+		}
+		None => debug!("pwd reset requested for unknown username '{username}'"),
+	}
+
+	// -- Create the success body.
+	let body = Json(json!({
+		"result": {
+			"success": true
+		}
+	}));
+
+	Ok(body)
+}
+
+#[derive(Debug, Deserialize)]
+struct PwdResetRequestPayload {
+	username: String,
+}
+// endregion: --- Request
+
+// region:    --- Confirm
+async fn api_pwd_reset_confirm_handler(
+	State(PwdResetState { mm, .. }): State<PwdResetState>,
+	Json(payload): Json<PwdResetConfirmPayload>,
+) -> Result<Json<Value>> {
+	debug!("{:<12} - api_pwd_reset_confirm_handler", "HANDLER");
+
+	let PwdResetConfirmPayload { token, pwd: pwd_clear } = payload;
+	let root_ctx = Ctx::root_ctx();
+
+	// -- Validate the token against the user current pwd.
+	let token: Token = token.parse().map_err(Error::PwdResetTokenInvalid)?;
+	let user: UserForLogin =
+		UserBmc::first_by_username(&root_ctx, &mm, &token.ident)
+			.await?
+			.ok_or(Error::PwdResetTokenInvalid(
+				lib_base::token::Error::UserNotMatching,
+			))?;
+	validate_pwd_reset_token(&token, &user).map_err(|ex| match ex {
+		Error::Token(token_error) => Error::PwdResetTokenInvalid(token_error),
+		ex => ex,
+	})?;
+
+	// -- Update the pwd and end all the user sessions (in one transaction).
+	pwd::validate_pwd_policy(&pwd_clear)?;
+	{
+		let mm = mm.begin().await?;
+		UserBmc::update_pwd(&root_ctx, &mm, user.id, &pwd_clear).await?;
+		UserBmc::update_token_salt(&root_ctx, &mm, user.id).await?;
+		SessionBmc::delete_by_user(&root_ctx, &mm, user.id, None).await?;
+		mm.commit().await?;
+	}
+
+	// -- Create the success body.
+	let body = Json(json!({
+		"result": {
+			"success": true
+		}
+	}));
+
+	Ok(body)
+}
+
+#[derive(Debug, Deserialize)]
+struct PwdResetConfirmPayload {
+	token: String,
+	pwd: String,
+}
+// endregion: --- Confirm
@@ -0,0 +1,39 @@
+use crate::web::rpc::{DataResult, ParamsIded};
+use crate::web::Result;
+use lib_core::ctx::Ctx;
+use lib_core::model::attachment::{Attachment, AttachmentBmc};
+use lib_core::model::ModelManager;
+use serde::Deserialize;
+
+// Note: Attachments are uploaded and downloaded with `routes_attachment`
+//       (multipart and streamed content).
+
+#[derive(Deserialize)]
+pub struct ParamsTaskIded {
+	task_id: i64,
+}
+
+pub async fn list_attachments(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsTaskIded,
+) -> Result<DataResult<Vec<Attachment>>> {
+	let ParamsTaskIded { task_id } = params;
+
+	let attachments = AttachmentBmc::list_by_task(&ctx, &mm, task_id).await?;
+
+	Ok(DataResult::new(attachments))
+}
+
+pub async fn delete_attachment(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsIded,
+) -> Result<DataResult<Attachment>> {
+	let ParamsIded { id } = params;
+
+	let attachment = AttachmentBmc::get(&ctx, &mm, id).await?;
+	AttachmentBmc::delete(&ctx, &mm, id).await?;
+
+	Ok(DataResult::new(attachment))
+}
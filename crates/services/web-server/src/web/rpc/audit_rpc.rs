@@ -0,0 +1,17 @@
+use crate::web::rpc::{DataResult, ParamsList};
+use crate::web::Result;
+use lib_core::ctx::Ctx;
+use lib_core::model::audit::{AuditBmc, AuditEntry, AuditEntryFilter};
+use lib_core::model::ModelManager;
+
+pub async fn list_audit_entries(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsList<AuditEntryFilter>,
+) -> Result<DataResult<Vec<AuditEntry>>> {
+	let ParamsList { filters, list_options } = params;
+
+	let page = AuditBmc::list_page(&ctx, &mm, filters, list_options).await?;
+
+	Ok(page.into())
+}
@@ -0,0 +1,68 @@
+use crate::web::rpc::{DataResult, ParamsForCreate, ParamsForUpdate, ParamsIded};
+use crate::web::Result;
+use lib_core::ctx::Ctx;
+use lib_core::model::comment::{
+	Comment, CommentBmc, CommentForCreate, CommentForUpdate,
+};
+use lib_core::model::filter::ListOptions;
+use lib_core::model::ModelManager;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct ParamsListByTask {
+	task_id: i64,
+	list_options: Option<ListOptions>,
+}
+
+pub async fn add_comment(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsForCreate<CommentForCreate>,
+) -> Result<DataResult<Comment>> {
+	let ParamsForCreate { data } = params;
+
+	let id = CommentBmc::create(&ctx, &mm, data).await?;
+	let comment = CommentBmc::get(&ctx, &mm, id).await?;
+
+	Ok(DataResult::new(comment))
+}
+
+pub async fn list_comments(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsListByTask,
+) -> Result<DataResult<Vec<Comment>>> {
+	let ParamsListByTask { task_id, list_options } = params;
+
+	let page =
+		CommentBmc::list_page_by_task(&ctx, &mm, task_id, list_options).await?;
+
+	Ok(page.into())
+}
+
+pub async fn edit_comment(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsForUpdate<CommentForUpdate>,
+) -> Result<DataResult<Comment>> {
+	let ParamsForUpdate { id, data } = params;
+
+	CommentBmc::update(&ctx, &mm, id, data).await?;
+
+	let comment = CommentBmc::get(&ctx, &mm, id).await?;
+
+	Ok(DataResult::new(comment))
+}
+
+pub async fn delete_comment(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsIded,
+) -> Result<DataResult<Comment>> {
+	let ParamsIded { id } = params;
+
+	let comment = CommentBmc::get(&ctx, &mm, id).await?;
+	CommentBmc::delete(&ctx, &mm, id).await?;
+
+	Ok(DataResult::new(comment))
+}
@@ -0,0 +1,61 @@
+use crate::web::rpc::{
+	DataResult, ParamsForCreate, ParamsForUpdate, ParamsIded, ParamsList,
+};
+use crate::web::Result;
+use lib_core::ctx::Ctx;
+use lib_core::model::label::{
+	Label, LabelBmc, LabelFilter, LabelForCreate, LabelForUpdate,
+};
+use lib_core::model::ModelManager;
+
+pub async fn create_label(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsForCreate<LabelForCreate>,
+) -> Result<DataResult<Label>> {
+	let ParamsForCreate { data } = params;
+
+	let id = LabelBmc::create(&ctx, &mm, data).await?;
+	let label = LabelBmc::get(&ctx, &mm, id).await?;
+
+	Ok(DataResult::new(label))
+}
+
+pub async fn list_labels(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsList<LabelFilter>,
+) -> Result<DataResult<Vec<Label>>> {
+	let ParamsList { filters, list_options } = params;
+
+	let page = LabelBmc::list_page(&ctx, &mm, filters, list_options).await?;
+
+	Ok(page.into())
+}
+
+pub async fn update_label(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsForUpdate<LabelForUpdate>,
+) -> Result<DataResult<Label>> {
+	let ParamsForUpdate { id, data } = params;
+
+	LabelBmc::update(&ctx, &mm, id, data).await?;
+
+	let label = LabelBmc::get(&ctx, &mm, id).await?;
+
+	Ok(DataResult::new(label))
+}
+
+pub async fn delete_label(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsIded,
+) -> Result<DataResult<Label>> {
+	let ParamsIded { id } = params;
+
+	let label = LabelBmc::get(&ctx, &mm, id).await?;
+	LabelBmc::delete(&ctx, &mm, id).await?;
+
+	Ok(DataResult::new(label))
+}
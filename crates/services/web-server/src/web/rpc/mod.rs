@@ -1,18 +1,47 @@
 // region:    --- Modules
 
+mod attachment_rpc;
+mod audit_rpc;
+mod comment_rpc;
+mod label_rpc;
+mod project_rpc;
+mod session_rpc;
 mod task_rpc;
+mod user_rpc;
+mod workspace_rpc;
 
 use crate::web::mw_auth::CtxW;
-use crate::web::rpc::task_rpc::{create_task, delete_task, list_tasks, update_task};
+use crate::web::rpc::attachment_rpc::{delete_attachment, list_attachments};
+use crate::web::rpc::audit_rpc::list_audit_entries;
+use crate::web::rpc::comment_rpc::{
+	add_comment, delete_comment, edit_comment, list_comments,
+};
+use crate::web::rpc::label_rpc::{
+	create_label, delete_label, list_labels, update_label,
+};
+use crate::web::rpc::project_rpc::{
+	create_project, delete_project, list_projects, update_project,
+};
+use crate::web::rpc::session_rpc::{list_sessions, revoke_session};
+use crate::web::rpc::task_rpc::{
+	add_task_blocker, assign_task, attach_task_label, count_tasks, create_task,
+	delete_task, detach_task_label, get_task, get_task_tree, list_tasks, move_task,
+	remove_task_blocker, restore_task, search_tasks, set_task_parent, update_task,
+};
+use crate::web::rpc::user_rpc::change_pwd;
+use crate::web::rpc::workspace_rpc::{create_workspace, list_workspaces};
 use crate::web::{Error, Result};
 use axum::extract::State;
 use axum::response::{IntoResponse, Response};
 use axum::routing::post;
 use axum::{Json, Router};
 use lib_core::ctx::Ctx;
+use lib_core::model::filter::{ListOptions, Page};
 use lib_core::model::ModelManager;
 use serde::{Deserialize, Serialize};
 use serde_json::{from_value, json, to_value, Value};
+use serde_with::skip_serializing_none;
+use tower_cookies::Cookies;
 use tracing::debug;
 
 // endregion: --- Modules
@@ -43,14 +72,34 @@ pub struct ParamsIded {
 	id: i64,
 }
 
+#[derive(Deserialize, Default)]
+pub struct ParamsFiltered<F> {
+	filters: Option<F>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct ParamsList<F> {
+	filters: Option<F>,
+	list_options: Option<ListOptions>,
+}
+
+#[skip_serializing_none]
 #[derive(Serialize)]
 pub struct DataResult<D> {
 	data: D,
+	next_cursor: Option<String>,
 }
 
 impl<D> DataResult<D> {
 	fn new(data: D) -> Self {
-		Self { data }
+		Self { data, next_cursor: None }
+	}
+}
+
+impl<E> From<Page<E>> for DataResult<Vec<E>> {
+	fn from(page: Page<E>) -> Self {
+		let Page { data, next_cursor } = page;
+		Self { data, next_cursor }
 	}
 }
 // endregion: --- RPC Types
@@ -64,6 +113,7 @@ pub fn routes(mm: ModelManager) -> Router {
 async fn rpc_handler(
 	State(mm): State<ModelManager>,
 	ctx: CtxW,
+	cookies: Cookies,
 	Json(rpc_req): Json<RpcRequest>,
 ) -> Response {
 	// -- Create the RPC Info to be set to the response.extensions.
@@ -73,7 +123,9 @@ async fn rpc_handler(
 	};
 
 	// -- Exec & Store RpcInfo in response.
-	let mut res = _rpc_handler(ctx.0, mm, rpc_req).await.into_response();
+	let mut res = _rpc_handler(ctx.0, mm, cookies, rpc_req)
+		.await
+		.into_response();
 	res.extensions_mut().insert(rpc_info);
 
 	res
@@ -86,10 +138,57 @@ pub struct RpcInfo {
 	pub method: String,
 }
 
+/// The access required by a RPC method, declared by each dispatch arm
+/// (so that a new RPC method cannot be added without one).
+#[derive(Clone, Copy)]
+enum RpcAccess {
+	/// Any authenticated user (i.e., the rpc fn scopes to the ctx user itself).
+	User,
+	/// The ctx must have the permission.
+	Perm(&'static str),
+}
+
+fn check_rpc_access(ctx: &Ctx, rpc_method: &str, access: RpcAccess) -> Result<()> {
+	match access {
+		RpcAccess::User => Ok(()),
+		RpcAccess::Perm(permission) if ctx.has_permission(permission) => Ok(()),
+		RpcAccess::Perm(permission) => Err(Error::RpcAccessDenied {
+			rpc_method: rpc_method.to_string(),
+			permission,
+		}),
+	}
+}
+
+/// The `RpcAccess` of the `exec_rpc_fn!` access token
+/// (`user` or the permission literal, e.g., `"task:read"`).
+macro_rules! rpc_access {
+	(user) => {
+		RpcAccess::User
+	};
+	($permission:literal) => {
+		RpcAccess::Perm($permission)
+	};
+}
+
 macro_rules! exec_rpc_fn {
+	// With the request cookies and params (e.g., to re-issue the token).
+	($rpc_fn:expr, $access:tt, $ctx:expr, $mm:expr, cookies: $cookies:expr, $rpc_params:expr) => {{
+		let rpc_fn_name = stringify!($rpc_fn);
+		check_rpc_access(&$ctx, rpc_fn_name, rpc_access!($access))?;
+		let params = $rpc_params.ok_or(Error::RpcMissingParams {
+			rpc_method: rpc_fn_name.to_string(),
+		})?;
+		let params = from_value(params).map_err(|_| Error::RpcFailJsonParams {
+			rpc_method: rpc_fn_name.to_string(),
+		})?;
+
+		$rpc_fn($ctx, $mm, $cookies, params).await.map(to_value)??
+	}};
+
 	// With params.
-	($rpc_fn:expr, $ctx:expr, $mm:expr, $rpc_params:expr) => {{
+	($rpc_fn:expr, $access:tt, $ctx:expr, $mm:expr, $rpc_params:expr) => {{
 		let rpc_fn_name = stringify!($rpc_fn);
+		check_rpc_access(&$ctx, rpc_fn_name, rpc_access!($access))?;
 		let params = $rpc_params.ok_or(Error::RpcMissingParams {
 			rpc_method: rpc_fn_name.to_string(),
 		})?;
@@ -100,15 +199,31 @@ macro_rules! exec_rpc_fn {
 		$rpc_fn($ctx, $mm, params).await.map(to_value)??
 	}};
 
+	// With optional params (default when absent).
+	($rpc_fn:expr, $access:tt, $ctx:expr, $mm:expr, $rpc_params:expr, default) => {{
+		let rpc_fn_name = stringify!($rpc_fn);
+		check_rpc_access(&$ctx, rpc_fn_name, rpc_access!($access))?;
+		let params = match $rpc_params {
+			Some(params) => from_value(params).map_err(|_| {
+				Error::RpcFailJsonParams { rpc_method: rpc_fn_name.to_string() }
+			})?,
+			None => Default::default(),
+		};
+
+		$rpc_fn($ctx, $mm, params).await.map(to_value)??
+	}};
+
 	// Without params.
-	($rpc_fn:expr, $ctx:expr, $mm:expr) => {
+	($rpc_fn:expr, $access:tt, $ctx:expr, $mm:expr) => {{
+		check_rpc_access(&$ctx, stringify!($rpc_fn), rpc_access!($access))?;
 		$rpc_fn($ctx, $mm).await.map(to_value)??
-	};
+	}};
 }
 
 async fn _rpc_handler(
 	ctx: Ctx,
 	mm: ModelManager,
+	cookies: Cookies,
 	rpc_req: RpcRequest,
 ) -> Result<Json<Value>> {
 	let RpcRequest {
@@ -120,11 +235,133 @@ async fn _rpc_handler(
 	debug!("{:<12} - _rpc_handler - method: {rpc_method}", "HANDLER");
 
 	let result_json = match rpc_method.as_str() {
+		// -- Project RPC methods.
+		"create_project" => {
+			exec_rpc_fn!(create_project, "project:create", ctx, mm, rpc_params)
+		}
+		"list_projects" => {
+			exec_rpc_fn!(list_projects, "project:read", ctx, mm, rpc_params, default)
+		}
+		"update_project" => {
+			exec_rpc_fn!(update_project, "project:update", ctx, mm, rpc_params)
+		}
+		"delete_project" => {
+			exec_rpc_fn!(delete_project, "project:delete", ctx, mm, rpc_params)
+		}
+
+		// -- Label RPC methods.
+		"create_label" => {
+			exec_rpc_fn!(create_label, "label:create", ctx, mm, rpc_params)
+		}
+		"list_labels" => {
+			exec_rpc_fn!(list_labels, "label:read", ctx, mm, rpc_params, default)
+		}
+		"update_label" => {
+			exec_rpc_fn!(update_label, "label:update", ctx, mm, rpc_params)
+		}
+		"delete_label" => {
+			exec_rpc_fn!(delete_label, "label:delete", ctx, mm, rpc_params)
+		}
+
 		// -- Task RPC methods.
-		"create_task" => exec_rpc_fn!(create_task, ctx, mm, rpc_params),
-		"list_tasks" => exec_rpc_fn!(list_tasks, ctx, mm),
-		"update_task" => exec_rpc_fn!(update_task, ctx, mm, rpc_params),
-		"delete_task" => exec_rpc_fn!(delete_task, ctx, mm, rpc_params),
+		"create_task" => {
+			exec_rpc_fn!(create_task, "task:create", ctx, mm, rpc_params)
+		}
+		"get_task" => {
+			exec_rpc_fn!(get_task, "task:read", ctx, mm, rpc_params)
+		}
+		"get_task_tree" => {
+			exec_rpc_fn!(get_task_tree, "task:read", ctx, mm, rpc_params)
+		}
+		"list_tasks" => {
+			exec_rpc_fn!(list_tasks, "task:read", ctx, mm, rpc_params, default)
+		}
+		"search_tasks" => {
+			exec_rpc_fn!(search_tasks, "task:read", ctx, mm, rpc_params)
+		}
+		"count_tasks" => {
+			exec_rpc_fn!(count_tasks, "task:read", ctx, mm, rpc_params, default)
+		}
+		"update_task" => {
+			exec_rpc_fn!(update_task, "task:update", ctx, mm, rpc_params)
+		}
+		"delete_task" => {
+			exec_rpc_fn!(delete_task, "task:delete", ctx, mm, rpc_params)
+		}
+		"restore_task" => {
+			exec_rpc_fn!(restore_task, "task:delete", ctx, mm, rpc_params)
+		}
+		"attach_task_label" => {
+			exec_rpc_fn!(attach_task_label, "task:update", ctx, mm, rpc_params)
+		}
+		"detach_task_label" => {
+			exec_rpc_fn!(detach_task_label, "task:update", ctx, mm, rpc_params)
+		}
+		"assign_task" => {
+			exec_rpc_fn!(assign_task, "task:update", ctx, mm, rpc_params)
+		}
+		"move_task" => exec_rpc_fn!(move_task, "task:update", ctx, mm, rpc_params),
+		"set_task_parent" => {
+			exec_rpc_fn!(set_task_parent, "task:update", ctx, mm, rpc_params)
+		}
+		"add_task_blocker" => {
+			exec_rpc_fn!(add_task_blocker, "task:update", ctx, mm, rpc_params)
+		}
+		"remove_task_blocker" => {
+			exec_rpc_fn!(remove_task_blocker, "task:update", ctx, mm, rpc_params)
+		}
+
+		// -- Comment RPC methods.
+		"add_comment" => {
+			exec_rpc_fn!(add_comment, "comment:create", ctx, mm, rpc_params)
+		}
+		"list_comments" => {
+			exec_rpc_fn!(list_comments, "comment:read", ctx, mm, rpc_params)
+		}
+		"edit_comment" => {
+			exec_rpc_fn!(edit_comment, "comment:update", ctx, mm, rpc_params)
+		}
+		"delete_comment" => {
+			exec_rpc_fn!(delete_comment, "comment:delete", ctx, mm, rpc_params)
+		}
+
+		// -- Attachment RPC methods.
+		// (create and read also checked by `routes_attachment`)
+		"list_attachments" => {
+			exec_rpc_fn!(list_attachments, "attachment:read", ctx, mm, rpc_params)
+		}
+		"delete_attachment" => {
+			exec_rpc_fn!(delete_attachment, "attachment:delete", ctx, mm, rpc_params)
+		}
+
+		// -- Audit RPC methods.
+		"list_audit_entries" => {
+			exec_rpc_fn!(
+				list_audit_entries,
+				"audit:read",
+				ctx,
+				mm,
+				rpc_params,
+				default
+			)
+		}
+
+		// -- Workspace RPC methods.
+		"create_workspace" => {
+			exec_rpc_fn!(create_workspace, user, ctx, mm, rpc_params)
+		}
+		"list_workspaces" => exec_rpc_fn!(list_workspaces, user, ctx, mm),
+
+		// -- User RPC methods.
+		"change_pwd" => {
+			exec_rpc_fn!(change_pwd, user, ctx, mm, cookies: &cookies, rpc_params)
+		}
+
+		// -- Session RPC methods.
+		"list_sessions" => exec_rpc_fn!(list_sessions, user, ctx, mm),
+		"revoke_session" => {
+			exec_rpc_fn!(revoke_session, user, ctx, mm, rpc_params)
+		}
 
 		// -- Fallback as Err.
 		_ => return Err(Error::RpcMethodUnknown(rpc_method)),
@@ -0,0 +1,61 @@
+use crate::web::rpc::{
+	DataResult, ParamsForCreate, ParamsForUpdate, ParamsIded, ParamsList,
+};
+use crate::web::Result;
+use lib_core::ctx::Ctx;
+use lib_core::model::project::{
+	Project, ProjectBmc, ProjectFilter, ProjectForCreate, ProjectForUpdate,
+};
+use lib_core::model::ModelManager;
+
+pub async fn create_project(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsForCreate<ProjectForCreate>,
+) -> Result<DataResult<Project>> {
+	let ParamsForCreate { data } = params;
+
+	let id = ProjectBmc::create(&ctx, &mm, data).await?;
+	let project = ProjectBmc::get(&ctx, &mm, id).await?;
+
+	Ok(DataResult::new(project))
+}
+
+pub async fn list_projects(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsList<ProjectFilter>,
+) -> Result<DataResult<Vec<Project>>> {
+	let ParamsList { filters, list_options } = params;
+
+	let page = ProjectBmc::list_page(&ctx, &mm, filters, list_options).await?;
+
+	Ok(page.into())
+}
+
+pub async fn update_project(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsForUpdate<ProjectForUpdate>,
+) -> Result<DataResult<Project>> {
+	let ParamsForUpdate { id, data } = params;
+
+	ProjectBmc::update(&ctx, &mm, id, data).await?;
+
+	let project = ProjectBmc::get(&ctx, &mm, id).await?;
+
+	Ok(DataResult::new(project))
+}
+
+pub async fn delete_project(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsIded,
+) -> Result<DataResult<Project>> {
+	let ParamsIded { id } = params;
+
+	let project = ProjectBmc::get(&ctx, &mm, id).await?;
+	ProjectBmc::delete(&ctx, &mm, id).await?;
+
+	Ok(DataResult::new(project))
+}
@@ -0,0 +1,29 @@
+use crate::web::rpc::{DataResult, ParamsIded};
+use crate::web::Result;
+use lib_core::ctx::Ctx;
+use lib_core::model::session::{Session, SessionBmc};
+use lib_core::model::ModelManager;
+
+// Note: Only the ctx user sessions (see `SessionBmc`), so no permission required.
+
+pub async fn list_sessions(
+	ctx: Ctx,
+	mm: ModelManager,
+) -> Result<DataResult<Vec<Session>>> {
+	let sessions = SessionBmc::list(&ctx, &mm).await?;
+
+	Ok(DataResult::new(sessions))
+}
+
+pub async fn revoke_session(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsIded,
+) -> Result<DataResult<Session>> {
+	let ParamsIded { id } = params;
+
+	let session = SessionBmc::get(&ctx, &mm, id).await?;
+	SessionBmc::delete(&ctx, &mm, id).await?;
+
+	Ok(DataResult::new(session))
+}
@@ -1,8 +1,56 @@
-use crate::web::rpc::{DataResult, ParamsForCreate, ParamsForUpdate, ParamsIded};
+use crate::web::rpc::{
+	DataResult, ParamsFiltered, ParamsForCreate, ParamsForUpdate, ParamsIded,
+	ParamsList,
+};
 use crate::web::Result;
 use lib_core::ctx::Ctx;
-use lib_core::model::task::{Task, TaskBmc, TaskForCreate, TaskForUpdate};
+use lib_core::model::filter::{ListOptions, SearchHit};
+use lib_core::model::task::{
+	Task, TaskBmc, TaskFilter, TaskForCreate, TaskForUpdate, TaskPositionTarget,
+	TaskTree,
+};
 use lib_core::model::ModelManager;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct ParamsTaskLabel {
+	task_id: i64,
+	label_id: i64,
+}
+
+#[derive(Deserialize)]
+pub struct ParamsTaskParent {
+	id: i64,
+	parent_id: Option<i64>,
+}
+
+/// e.g., `{"id": 1001, "before_id": 1002}` or `{"id": 1001, "after_id": 1002}`
+#[derive(Deserialize)]
+pub struct ParamsTaskMove {
+	id: i64,
+	#[serde(flatten)]
+	target: TaskPositionTarget,
+}
+
+#[derive(Deserialize)]
+pub struct ParamsTaskAssign {
+	id: i64,
+	assignee_id: Option<i64>,
+}
+
+/// e.g., `{"search": "release -draft", "filters": {"status": {"$eq": "todo"}}}`
+#[derive(Deserialize)]
+pub struct ParamsTaskSearch {
+	search: String,
+	filters: Option<TaskFilter>,
+	list_options: Option<ListOptions>,
+}
+
+#[derive(Deserialize)]
+pub struct ParamsTaskBlocker {
+	task_id: i64,
+	blocker_id: i64,
+}
 
 pub async fn create_task(
 	ctx: Ctx,
@@ -17,13 +65,64 @@ pub async fn create_task(
 	Ok(DataResult::new(task))
 }
 
+pub async fn get_task(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsIded,
+) -> Result<DataResult<Task>> {
+	let ParamsIded { id } = params;
+
+	let task = TaskBmc::get(&ctx, &mm, id).await?;
+
+	Ok(DataResult::new(task))
+}
+
+pub async fn get_task_tree(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsIded,
+) -> Result<DataResult<TaskTree>> {
+	let ParamsIded { id } = params;
+
+	let tree = TaskBmc::get_tree(&ctx, &mm, id).await?;
+
+	Ok(DataResult::new(tree))
+}
+
 pub async fn list_tasks(
 	ctx: Ctx,
 	mm: ModelManager,
+	params: ParamsList<TaskFilter>,
 ) -> Result<DataResult<Vec<Task>>> {
-	let tasks = TaskBmc::list(&ctx, &mm).await?;
+	let ParamsList { filters, list_options } = params;
+
+	let page = TaskBmc::list_page(&ctx, &mm, filters, list_options).await?;
+
+	Ok(page.into())
+}
+
+pub async fn search_tasks(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsTaskSearch,
+) -> Result<DataResult<Vec<SearchHit<Task>>>> {
+	let ParamsTaskSearch { search, filters, list_options } = params;
+
+	let page = TaskBmc::search(&ctx, &mm, &search, filters, list_options).await?;
+
+	Ok(page.into())
+}
+
+pub async fn count_tasks(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsFiltered<TaskFilter>,
+) -> Result<DataResult<i64>> {
+	let ParamsFiltered { filters } = params;
 
-	Ok(DataResult::new(tasks))
+	let count = TaskBmc::count(&ctx, &mm, filters).await?;
+
+	Ok(DataResult::new(count))
 }
 
 pub async fn update_task(
@@ -52,3 +151,107 @@ pub async fn delete_task(
 
 	Ok(DataResult::new(task))
 }
+
+pub async fn restore_task(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsIded,
+) -> Result<DataResult<Task>> {
+	let ParamsIded { id } = params;
+
+	TaskBmc::restore(&ctx, &mm, id).await?;
+	let task = TaskBmc::get(&ctx, &mm, id).await?;
+
+	Ok(DataResult::new(task))
+}
+
+pub async fn attach_task_label(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsTaskLabel,
+) -> Result<DataResult<Task>> {
+	let ParamsTaskLabel { task_id, label_id } = params;
+
+	TaskBmc::attach_label(&ctx, &mm, task_id, label_id).await?;
+	let task = TaskBmc::get(&ctx, &mm, task_id).await?;
+
+	Ok(DataResult::new(task))
+}
+
+pub async fn detach_task_label(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsTaskLabel,
+) -> Result<DataResult<Task>> {
+	let ParamsTaskLabel { task_id, label_id } = params;
+
+	TaskBmc::detach_label(&ctx, &mm, task_id, label_id).await?;
+	let task = TaskBmc::get(&ctx, &mm, task_id).await?;
+
+	Ok(DataResult::new(task))
+}
+
+pub async fn assign_task(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsTaskAssign,
+) -> Result<DataResult<Task>> {
+	let ParamsTaskAssign { id, assignee_id } = params;
+
+	TaskBmc::assign(&ctx, &mm, id, assignee_id).await?;
+	let task = TaskBmc::get(&ctx, &mm, id).await?;
+
+	Ok(DataResult::new(task))
+}
+
+pub async fn move_task(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsTaskMove,
+) -> Result<DataResult<Task>> {
+	let ParamsTaskMove { id, target } = params;
+
+	TaskBmc::move_to_position(&ctx, &mm, id, target).await?;
+	let task = TaskBmc::get(&ctx, &mm, id).await?;
+
+	Ok(DataResult::new(task))
+}
+
+pub async fn set_task_parent(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsTaskParent,
+) -> Result<DataResult<Task>> {
+	let ParamsTaskParent { id, parent_id } = params;
+
+	TaskBmc::move_to_parent(&ctx, &mm, id, parent_id).await?;
+	let task = TaskBmc::get(&ctx, &mm, id).await?;
+
+	Ok(DataResult::new(task))
+}
+
+pub async fn add_task_blocker(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsTaskBlocker,
+) -> Result<DataResult<Task>> {
+	let ParamsTaskBlocker { task_id, blocker_id } = params;
+
+	TaskBmc::add_blocker(&ctx, &mm, task_id, blocker_id).await?;
+	let task = TaskBmc::get(&ctx, &mm, task_id).await?;
+
+	Ok(DataResult::new(task))
+}
+
+pub async fn remove_task_blocker(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsTaskBlocker,
+) -> Result<DataResult<Task>> {
+	let ParamsTaskBlocker { task_id, blocker_id } = params;
+
+	TaskBmc::remove_blocker(&ctx, &mm, task_id, blocker_id).await?;
+	let task = TaskBmc::get(&ctx, &mm, task_id).await?;
+
+	Ok(DataResult::new(task))
+}
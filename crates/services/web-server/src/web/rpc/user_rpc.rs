@@ -0,0 +1,30 @@
+use crate::web::routes_login;
+use crate::web::rpc::DataResult;
+use crate::web::Result;
+use lib_core::ctx::Ctx;
+use lib_core::model::user::{User, UserBmc};
+use lib_core::model::ModelManager;
+use serde::Deserialize;
+use tower_cookies::Cookies;
+
+#[derive(Deserialize)]
+pub struct ParamsChangePwd {
+	pwd: String,
+	new_pwd: String,
+}
+
+/// Note: Re-issues the caller token cookie, since the `token_salt` is regenerated
+///       (see `routes_login::change_pwd`).
+pub async fn change_pwd(
+	ctx: Ctx,
+	mm: ModelManager,
+	cookies: &Cookies,
+	params: ParamsChangePwd,
+) -> Result<DataResult<User>> {
+	let ParamsChangePwd { pwd, new_pwd } = params;
+
+	routes_login::change_pwd(&ctx, &mm, cookies, pwd, new_pwd).await?;
+	let user = UserBmc::get(&ctx, &mm, ctx.user_id()).await?;
+
+	Ok(DataResult::new(user))
+}
@@ -0,0 +1,27 @@
+use crate::web::rpc::{DataResult, ParamsForCreate};
+use crate::web::Result;
+use lib_core::ctx::Ctx;
+use lib_core::model::workspace::{Workspace, WorkspaceBmc, WorkspaceForCreate};
+use lib_core::model::ModelManager;
+
+pub async fn create_workspace(
+	ctx: Ctx,
+	mm: ModelManager,
+	params: ParamsForCreate<WorkspaceForCreate>,
+) -> Result<DataResult<Workspace>> {
+	let ParamsForCreate { data } = params;
+
+	let id = WorkspaceBmc::create(&ctx, &mm, data).await?;
+	let workspace = WorkspaceBmc::get(&ctx, &mm, id).await?;
+
+	Ok(DataResult::new(workspace))
+}
+
+pub async fn list_workspaces(
+	ctx: Ctx,
+	mm: ModelManager,
+) -> Result<DataResult<Vec<Workspace>>> {
+	let workspaces = WorkspaceBmc::list(&ctx, &mm).await?;
+
+	Ok(DataResult::new(workspaces))
+}
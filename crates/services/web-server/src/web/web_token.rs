@@ -2,10 +2,10 @@ use crate::web::Result;
 use lib_base::token::{generate_token, validate_token, Token};
 use lib_core::config;
 
-pub fn generate_web_token(user: &str, salt: &str) -> Result<Token> {
+pub fn generate_web_token(ident: &str, salt: &str) -> Result<Token> {
 	let config = &config();
 	let token =
-		generate_token(user, config.TOKEN_DURATION_SEC, salt, &config.TOKEN_KEY)?;
+		generate_token(ident, config.TOKEN_DURATION_SEC, salt, &config.TOKEN_KEY)?;
 
 	Ok(token)
 }
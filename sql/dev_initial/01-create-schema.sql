@@ -2,6 +2,12 @@
 ---- - Timestamps 
 ----   - cid/ctime for the creator id and time. 
 ----   - mid/mtime for the last modifier id and time.
+---- - Soft delete (for the `DbBmc::SOFT_DELETE` entities)
+----   - deleted_by/deleted_at for the deleter id and time (NULL when not deleted).
+---- - Version (for the `DbBmc::HAS_VERSION` entities)
+----   - version incremented on each update, for optimistic concurrency.
+---- - Workspace (for the `DbBmc::WORKSPACE_SCOPED` entities)
+----   - workspace_id of the workspace (i.e., tenant) the row belongs to.
 
 -- User
 CREATE TABLE "user" (
@@ -10,6 +16,10 @@ CREATE TABLE "user" (
   username varchar(128) NOT NULL UNIQUE,
   username_norm varchar(128) NOT NULL UNIQUE,      -- column trigger generated (see below)
 
+  -- Email (lowercase), verified with an email verification token
+  email varchar(256) UNIQUE,
+  email_verified_at timestamp with time zone,
+
   -- Auth
   pwd varchar(256),
   pwd_salt uuid NOT NULL DEFAULT gen_random_uuid(),
@@ -43,15 +53,250 @@ FOR EACH ROW
 EXECUTE FUNCTION user_username_norm_tg_fn();
 
 
+-- Session (of a web token, by `ident`)
+CREATE TABLE session (
+  id bigint GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+
+  user_id bigint NOT NULL REFERENCES "user"(id) ON DELETE CASCADE,
+  ident uuid NOT NULL UNIQUE DEFAULT gen_random_uuid(),
+  user_agent varchar(512),
+  last_seen_at timestamp with time zone NOT NULL,
+
+  -- Timestamps
+  cid bigint NOT NULL,
+  ctime timestamp with time zone NOT NULL,
+  mid bigint NOT NULL,
+  mtime timestamp with time zone NOT NULL
+);
+
+CREATE INDEX session_user_id_idx ON session (user_id);
+
+
+-- Workspace (i.e., tenant)
+CREATE TABLE workspace (
+  id bigint GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+
+  name varchar(256) NOT NULL,
+
+  -- Timestamps
+  cid bigint NOT NULL,
+  ctime timestamp with time zone NOT NULL,
+  mid bigint NOT NULL,
+  mtime timestamp with time zone NOT NULL  
+);
+
+-- Workspace Member
+CREATE TABLE workspace_member (
+  workspace_id bigint NOT NULL REFERENCES workspace(id) ON DELETE CASCADE,
+  user_id bigint NOT NULL REFERENCES "user"(id) ON DELETE CASCADE,
+
+  PRIMARY KEY (workspace_id, user_id)
+);
+
+-- Role (RBAC)
+CREATE TABLE role (
+  id bigint GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+
+  name varchar(128) NOT NULL UNIQUE
+);
+
+-- Permission of a role (e.g., 'task:delete')
+CREATE TABLE permission (
+  id bigint GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+
+  role_id bigint NOT NULL REFERENCES role(id) ON DELETE CASCADE,
+  name varchar(128) NOT NULL,
+
+  UNIQUE (role_id, name)
+);
+
+-- User Role
+CREATE TABLE user_role (
+  user_id bigint NOT NULL REFERENCES "user"(id) ON DELETE CASCADE,
+  role_id bigint NOT NULL REFERENCES role(id) ON DELETE CASCADE,
+
+  PRIMARY KEY (user_id, role_id)
+);
+
+-- Project
+CREATE TABLE project (
+  id bigint GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+
+  workspace_id bigint NOT NULL REFERENCES workspace(id) ON DELETE CASCADE,
+
+  name varchar(256) NOT NULL,
+  description text,
+
+  -- Timestamps
+  cid bigint NOT NULL,
+  ctime timestamp with time zone NOT NULL,
+  mid bigint NOT NULL,
+  mtime timestamp with time zone NOT NULL  
+);
+
 -- Task
+CREATE TYPE task_status AS ENUM ('todo', 'in_progress', 'done', 'cancelled');
+
 CREATE TABLE task (
   id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
 
+  workspace_id bigint NOT NULL REFERENCES workspace(id) ON DELETE CASCADE,
+  project_id bigint REFERENCES project(id) ON DELETE CASCADE,
+  parent_id bigint REFERENCES task(id) ON DELETE CASCADE,
+  assignee_id bigint REFERENCES "user"(id) ON DELETE SET NULL,
+
   title varchar(256) NOT NULL,
+  description text,
+  status task_status NOT NULL DEFAULT 'todo',
+  priority integer NOT NULL DEFAULT 0,
+  due_at timestamp with time zone,
+  recurrence varchar(256), -- RRULE subset (see `model::recurrence`)
+  position text COLLATE "C" NOT NULL, -- lexicographic key (see `lib_base::position`)
+  done_at timestamp with time zone,  -- column trigger generated (see below)
+
+  -- Full-text search (see `base::search_page`), title matches ranked first.
+  search_tsv tsvector GENERATED ALWAYS AS (
+    setweight(to_tsvector('english', coalesce(title, '')), 'A') ||
+    setweight(to_tsvector('english', coalesce(description, '')), 'B')
+  ) STORED,
+
+  -- Version
+  version bigint NOT NULL DEFAULT 0,
+
+  -- Soft delete
+  deleted_by bigint,
+  deleted_at timestamp with time zone,
 
   -- Timestamps
   cid bigint NOT NULL,
   ctime timestamp with time zone NOT NULL,
   mid bigint NOT NULL,
   mtime timestamp with time zone NOT NULL  
-);
\ No newline at end of file
+);
+
+-- Set the task.done_at when the status becomes 'done' (and clear it otherwise).
+CREATE OR REPLACE FUNCTION task_done_at_tg_fn()
+RETURNS TRIGGER AS $$
+BEGIN
+  IF NEW.status <> 'done' THEN
+    NEW.done_at := NULL;
+  ELSIF TG_OP = 'INSERT' OR OLD.status <> 'done' THEN
+    NEW.done_at := now();
+  END IF;
+
+  RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER task_done_at_tg
+BEFORE INSERT OR UPDATE OF status
+ON task
+FOR EACH ROW
+EXECUTE FUNCTION task_done_at_tg_fn();
+
+CREATE INDEX task_parent_id_idx ON task (parent_id);
+CREATE INDEX task_assignee_id_idx ON task (assignee_id);
+CREATE UNIQUE INDEX task_workspace_id_position_idx ON task (workspace_id, position);
+CREATE INDEX task_search_tsv_idx ON task USING GIN (search_tsv);
+
+-- Task Dependency (`blocker_id` blocks `task_id`)
+CREATE TABLE task_dependency (
+  task_id bigint NOT NULL REFERENCES task(id) ON DELETE CASCADE,
+  blocker_id bigint NOT NULL REFERENCES task(id) ON DELETE CASCADE,
+
+  PRIMARY KEY (task_id, blocker_id),
+  CHECK (task_id <> blocker_id)
+);
+
+CREATE INDEX task_dependency_blocker_id_idx ON task_dependency (blocker_id);
+
+-- Label
+CREATE TABLE label (
+  id bigint GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+
+  workspace_id bigint NOT NULL REFERENCES workspace(id) ON DELETE CASCADE,
+
+  name varchar(128) NOT NULL,
+  color varchar(32),
+
+  -- Timestamps
+  cid bigint NOT NULL,
+  ctime timestamp with time zone NOT NULL,
+  mid bigint NOT NULL,
+  mtime timestamp with time zone NOT NULL,
+
+  UNIQUE (workspace_id, name)
+);
+
+-- Task Label (many-to-many)
+CREATE TABLE task_label (
+  task_id bigint NOT NULL REFERENCES task(id) ON DELETE CASCADE,
+  label_id bigint NOT NULL REFERENCES label(id) ON DELETE CASCADE,
+
+  PRIMARY KEY (task_id, label_id)
+);
+
+CREATE INDEX task_label_label_id_idx ON task_label (label_id);
+
+-- Comment (of a task, `cid` being the author)
+CREATE TABLE comment (
+  id bigint GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+
+  workspace_id bigint NOT NULL REFERENCES workspace(id) ON DELETE CASCADE,
+  task_id bigint NOT NULL REFERENCES task(id) ON DELETE CASCADE,
+
+  body text NOT NULL,
+
+  -- Timestamps
+  cid bigint NOT NULL,
+  ctime timestamp with time zone NOT NULL,
+  mid bigint NOT NULL,
+  mtime timestamp with time zone NOT NULL
+);
+
+CREATE INDEX comment_task_id_idx ON comment (task_id);
+
+-- Attachment (content in the blob store, by `blob_key`)
+CREATE TABLE attachment (
+  id bigint GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+
+  workspace_id bigint NOT NULL REFERENCES workspace(id) ON DELETE CASCADE,
+  task_id bigint NOT NULL REFERENCES task(id) ON DELETE CASCADE,
+
+  file_name varchar(256) NOT NULL,
+  content_type varchar(128) NOT NULL, -- sniffed from the content
+  size bigint NOT NULL,
+  checksum varchar(64) NOT NULL, -- SHA-256 (hex) of the content
+  blob_key varchar(64) NOT NULL UNIQUE,
+
+  -- Timestamps
+  cid bigint NOT NULL,
+  ctime timestamp with time zone NOT NULL,
+  mid bigint NOT NULL,
+  mtime timestamp with time zone NOT NULL
+);
+
+CREATE INDEX attachment_task_id_idx ON attachment (task_id);
+
+-- Audit Log (written by `model::base` on each create/update/delete)
+CREATE TABLE audit_log (
+  id bigint GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+
+  -- The ctx workspace of the write (NULL when none, e.g., user and session writes)
+  -- Note: No FK, so that the log outlives the workspace.
+  workspace_id bigint,
+
+  entity varchar(128) NOT NULL,
+  entity_id bigint NOT NULL,
+  action varchar(32) NOT NULL,        -- create, update, delete, restore, purge
+  user_id bigint NOT NULL,
+
+  before jsonb,
+  after jsonb,
+
+  req_uuid uuid,
+  ctime timestamp with time zone NOT NULL
+);
+
+CREATE INDEX audit_log_entity_idx ON audit_log (entity, entity_id);
+CREATE INDEX audit_log_workspace_id_idx ON audit_log (workspace_id);
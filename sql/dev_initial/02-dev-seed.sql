@@ -1,4 +1,37 @@
 -- User demo1
 INSERT INTO "user" 
-    (username, cid, ctime, mid, mtime) VALUES 
-    ('demo1',  0,   now(), 0,   now());
\ No newline at end of file
+    (username, email,               email_verified_at, cid, ctime, mid, mtime) VALUES 
+    ('demo1',  'demo1@example.com', now(),             0,   now(), 0,   now());
+
+-- Workspace demo (id 1000, see `_dev_utils::DEMO_WORKSPACE_ID`)
+INSERT INTO workspace
+    (name,   cid, ctime, mid, mtime) VALUES
+    ('demo', 0,   now(), 0,   now());
+
+-- Workspace Members - demo1 in demo
+INSERT INTO workspace_member (workspace_id, user_id)
+    SELECT workspace.id, "user".id FROM workspace, "user"
+    WHERE workspace.name = 'demo' AND "user".username = 'demo1';
+
+-- Roles
+INSERT INTO role (name) VALUES ('admin'), ('member');
+
+-- Permissions
+INSERT INTO permission (role_id, name)
+    SELECT role.id, perm.name FROM role, 
+      unnest(ARRAY[
+        'project:create', 'project:read', 'project:update', 'project:delete',
+        'label:create', 'label:read', 'label:update', 'label:delete',
+        'task:create', 'task:read', 'task:update', 'task:delete',
+        'comment:create', 'comment:read', 'comment:update', 'comment:delete',
+        'attachment:create', 'attachment:read', 'attachment:delete'
+      ]) AS perm(name)
+    WHERE role.name IN ('admin', 'member');
+
+INSERT INTO permission (role_id, name)
+    SELECT role.id, 'audit:read' FROM role WHERE role.name = 'admin';
+
+-- User Roles - demo1 member
+INSERT INTO user_role (user_id, role_id)
+    SELECT "user".id, role.id FROM "user", role
+    WHERE "user".username = 'demo1' AND role.name = 'member';